@@ -1,8 +1,15 @@
 use openssl::symm::{encrypt, Cipher, Crypter, Mode};
 use rand::{self, distributions::Standard, Rng};
 
+use gf128::{self, Element};
 use utility::error::{Result, ResultExt};
 
+pub struct GcmMessage {
+    pub aad: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+    pub tag: Vec<u8>,
+}
+
 pub fn decrypt_ecb_text(ciphertext_bytes: &[u8], key: &[u8]) -> Result<Vec<u8>> {
     let cipher = Cipher::aes_128_ecb();
     let mut plaintext = Vec::with_capacity(ciphertext_bytes.len());
@@ -100,6 +107,80 @@ pub fn encrypt_cbc_text(plaintext_bytes: &[u8], key: &[u8], iv: &[u8]) -> Result
     Ok(ciphertext)
 }
 
+pub fn ctr_text(text_bytes: &[u8], key: &[u8], nonce: u64) -> Result<Vec<u8>> {
+    let cipher = Cipher::aes_128_ecb();
+    let mut output = Vec::with_capacity(text_bytes.len());
+
+    for (counter, text_block) in text_bytes.chunks(cipher.block_size()).enumerate() {
+        // 64 bit little endian nonce, then 64 bit little endian block count
+        let mut counter_block = Vec::with_capacity(cipher.block_size());
+        counter_block.extend_from_slice(&nonce.to_le_bytes());
+        counter_block.extend_from_slice(&(counter as u64).to_le_bytes());
+
+        let keystream_block = encrypt_ecb_text(&counter_block, key)?;
+
+        output.extend(
+            text_block
+                .iter()
+                .zip(keystream_block)
+                .map(|(text_byte, keystream_byte)| text_byte ^ keystream_byte),
+        );
+    }
+
+    Ok(output)
+}
+
+// Only 96 bit nonces are supported, which GCM pads with a 32 bit counter starting at 1
+pub fn encrypt_gcm_text(
+    plaintext_bytes: &[u8],
+    aad: &[u8],
+    key: &[u8],
+    nonce: &[u8],
+) -> Result<GcmMessage> {
+    let ciphertext = gcm_ctr_text(plaintext_bytes, key, nonce)?;
+    let tag = gcm_tag(aad, &ciphertext, key, nonce)?;
+
+    Ok(GcmMessage {
+        aad: aad.to_vec(),
+        ciphertext,
+        tag,
+    })
+}
+
+// Tags shorter than a block are checked against the start of the full tag. The standard
+// stops at 32 bits, but shorter tags are allowed so truncation attacks can be tried cheaply
+pub fn decrypt_gcm_text(message: &GcmMessage, key: &[u8], nonce: &[u8]) -> Result<Vec<u8>> {
+    if !(1..=16).contains(&message.tag.len()) {
+        bail!("tag must be between 1 and 16 bytes")
+    }
+
+    let tag = gcm_tag(&message.aad, &message.ciphertext, key, nonce)?;
+
+    if tag[..message.tag.len()] != message.tag[..] {
+        bail!("invalid tag")
+    }
+
+    gcm_ctr_text(&message.ciphertext, key, nonce)
+}
+
+pub fn gcm_authentication_key(key: &[u8]) -> Result<Element> {
+    Element::from_bytes(&encrypt_ecb_text(&[0; 16], key)?)
+}
+
+pub fn cbc_mac(message: &[u8], key: &[u8], iv: &[u8]) -> Result<Vec<u8>> {
+    if message.is_empty() {
+        bail!("cannot MAC an empty message")
+    }
+
+    let mut padded_message = message.to_vec();
+    pkcs7_pad_vec(&mut padded_message, iv.len())?;
+
+    let mut ciphertext = encrypt_cbc_text(&padded_message, key, iv)?;
+    let mac_start = ciphertext.len() - iv.len();
+
+    Ok(ciphertext.split_off(mac_start))
+}
+
 pub fn pkcs7_pad_vec(byte_vec: &mut Vec<u8>, block_size: usize) -> Result<usize> {
     let padded_len = padded_len(byte_vec.len(), block_size);
     let padding_size = padded_len - byte_vec.len();
@@ -142,3 +223,40 @@ pub fn padded_len(length: usize, block_size: usize) -> usize {
 pub fn generate_random_aes_key(rng: &mut rand::ThreadRng, block_size: usize) -> Vec<u8> {
     rng.sample_iter(&Standard).take(block_size).collect()
 }
+
+fn gcm_counter_block(nonce: &[u8], counter: u32) -> Result<Vec<u8>> {
+    if nonce.len() != 12 {
+        bail!("GCM nonces must be 96 bits")
+    }
+
+    let mut counter_block = nonce.to_vec();
+    counter_block.extend_from_slice(&counter.to_be_bytes());
+
+    Ok(counter_block)
+}
+
+// The first counter block is reserved for masking the tag
+fn gcm_ctr_text(text_bytes: &[u8], key: &[u8], nonce: &[u8]) -> Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(text_bytes.len());
+
+    for (counter, text_block) in text_bytes.chunks(gf128::BLOCK_SIZE).enumerate() {
+        let counter_block = gcm_counter_block(nonce, counter as u32 + 2)?;
+        let keystream_block = encrypt_ecb_text(&counter_block, key)?;
+
+        output.extend(
+            text_block
+                .iter()
+                .zip(keystream_block)
+                .map(|(text_byte, keystream_byte)| text_byte ^ keystream_byte),
+        );
+    }
+
+    Ok(output)
+}
+
+fn gcm_tag(aad: &[u8], ciphertext: &[u8], key: &[u8], nonce: &[u8]) -> Result<Vec<u8>> {
+    let h = gcm_authentication_key(key)?;
+    let mask = Element::from_bytes(&encrypt_ecb_text(&gcm_counter_block(nonce, 1)?, key)?)?;
+
+    Ok((gf128::ghash(h, aad, ciphertext)? + mask).to_bytes())
+}
@@ -8,6 +8,7 @@ use utility::error::{Result, ResultExt};
 pub enum Mode {
     Ecb,
     Cbc,
+    Ctr,
     None,
 }
 
@@ -21,7 +22,7 @@ where
 
         match detect_encryption_mode(&line_bytes, 16) {
             Mode::Ecb => return Ok(line_number + 1),
-            Mode::Cbc | Mode::None => {}
+            Mode::Cbc | Mode::Ctr | Mode::None => {}
         }
     }
 
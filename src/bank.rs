@@ -0,0 +1,156 @@
+use rand;
+
+use std::collections::HashMap;
+
+use aes;
+use utility::error::Result;
+
+pub struct Bank {
+    pub block_size: usize,
+    pub key: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Transfer {
+    pub from: u64,
+    pub to: u64,
+    pub amount: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TransactionList {
+    pub from: u64,
+    pub transactions: Vec<(u64, u64)>,
+}
+
+impl Default for Bank {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bank {
+    pub fn new() -> Self {
+        let block_size = 16;
+        let mut rng = rand::thread_rng();
+
+        Bank {
+            block_size,
+            key: aes::generate_random_aes_key(&mut rng, block_size),
+        }
+    }
+
+    // message || IV || MAC, with the IV picked by the client
+    pub fn transfer_request(&self, from: u64, to: u64, amount: u64) -> Result<Vec<u8>> {
+        let mut rng = rand::thread_rng();
+        let iv = aes::generate_random_aes_key(&mut rng, self.block_size);
+        let message = format!("from={}&to={}&amount={}", from, to, amount);
+
+        let mut request = Vec::from(message);
+        let mac = aes::cbc_mac(&request, &self.key, &iv)?;
+        request.extend_from_slice(&iv);
+        request.extend_from_slice(&mac);
+
+        Ok(request)
+    }
+
+    pub fn verify_transfer_request(&self, request: &[u8]) -> Result<Transfer> {
+        if request.len() <= 2 * self.block_size {
+            bail!("request is too short")
+        }
+
+        let (message, iv_mac) = request.split_at(request.len() - 2 * self.block_size);
+        let (iv, mac) = iv_mac.split_at(self.block_size);
+
+        if aes::cbc_mac(message, &self.key, iv)? != mac {
+            bail!("invalid MAC")
+        }
+
+        let fields = parse_fields(message);
+
+        Ok(Transfer {
+            from: parse_field(&fields, "from")?,
+            to: parse_field(&fields, "to")?,
+            amount: parse_field(&fields, "amount")?,
+        })
+    }
+
+    // message || MAC, always under a zero IV
+    pub fn transaction_list_request(
+        &self,
+        from: u64,
+        transactions: &[(u64, u64)],
+    ) -> Result<Vec<u8>> {
+        let transaction_list = transactions
+            .iter()
+            .map(|&(to, amount)| format!("{}:{}", to, amount))
+            .collect::<Vec<String>>()
+            .join(";");
+        let message = format!("from={}&tx_list={}", from, transaction_list);
+
+        let mut request = Vec::from(message);
+        let mac = aes::cbc_mac(&request, &self.key, &vec![0; self.block_size])?;
+        request.extend_from_slice(&mac);
+
+        Ok(request)
+    }
+
+    pub fn verify_transaction_list_request(&self, request: &[u8]) -> Result<TransactionList> {
+        if request.len() <= self.block_size {
+            bail!("request is too short")
+        }
+
+        let (message, mac) = request.split_at(request.len() - self.block_size);
+
+        if aes::cbc_mac(message, &self.key, &vec![0; self.block_size])? != mac {
+            bail!("invalid MAC")
+        }
+
+        // Everything after the first `&tx_list=` is the transaction list
+        let message = String::from_utf8_lossy(message);
+        let (from, transaction_list) = match message.find("&tx_list=") {
+            Some(index) => (&message[..index], &message[index + "&tx_list=".len()..]),
+            None => bail!("missing field tx_list"),
+        };
+
+        // Entries that don't parse as `to:amount` are skipped over
+        let transactions = transaction_list
+            .split(';')
+            .filter_map(|transaction| {
+                let mut parts = transaction.splitn(2, ':');
+                match (parts.next(), parts.next()) {
+                    (Some(to), Some(amount)) => match (to.parse(), amount.parse()) {
+                        (Ok(to), Ok(amount)) => Some((to, amount)),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            })
+            .collect();
+
+        Ok(TransactionList {
+            from: parse_field(&parse_fields(from.as_bytes()), "from")?,
+            transactions,
+        })
+    }
+}
+
+fn parse_fields(message: &[u8]) -> HashMap<String, String> {
+    String::from_utf8_lossy(message)
+        .split('&')
+        .filter_map(|pair| {
+            let mut parts = pair.splitn(2, '=');
+            match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) => Some((String::from(key), String::from(value))),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn parse_field(fields: &HashMap<String, String>, name: &str) -> Result<u64> {
+    match fields.get(name).map(|value| value.parse()) {
+        Some(Ok(value)) => Ok(value),
+        _ => bail!("missing or invalid field {}", name),
+    }
+}
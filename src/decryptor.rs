@@ -1,12 +1,33 @@
 use hex;
+use num_bigint::{BigInt, BigUint, RandBigInt};
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, ToPrimitive, Zero};
+use rand::{self, Rng};
 
+use std::cmp;
 use std::io::BufRead;
 use std::str;
+use std::thread;
 
+use aes::{self, GcmMessage};
 use analyzer;
+use dh::{self, DhParameters};
+use dlog::{self, KangarooParameters, MultiplicativeGroup};
+use dsa::{self, Signature};
+use ec::ecdh::{self, EcdhParameters, LadderEcdhParameters};
+use ec::ecdsa::{self, EcdsaParameters};
+use ec::montgomery::MontgomeryCurve;
+use ec::weierstrass::Point;
+use gf128::polynomial::{self, Polynomial};
+use gf128::{self, Element};
+use gf2::Matrix;
+use lattice;
+use rsa;
 use utility::english;
 use utility::error::{Result, ResultExt};
 use utility::hamming;
+use utility::number;
 
 pub struct ByteKeyState {
     pub score: f32,
@@ -15,6 +36,11 @@ pub struct ByteKeyState {
     pub string: String,
 }
 
+pub struct DsaKeyState {
+    pub colliding_pairs: Vec<(usize, usize)>,
+    pub x: BigUint,
+}
+
 pub struct KeyState {
     pub distance: f32,
     pub size: usize,
@@ -283,3 +309,1084 @@ where
 
     Ok((matching_blocks, plaintext, first_ciphertext))
 }
+
+pub fn break_unpadded_rsa_fn<F>(
+    ciphertext: &BigUint,
+    public_key: &rsa::PublicKey,
+    oracle_fn: &mut F,
+) -> Result<BigUint>
+where
+    F: FnMut(&BigUint) -> Result<BigUint>,
+{
+    let mut rng = rand::thread_rng();
+
+    // Pick a random blinding factor that's invertible mod n
+    let (blinding_factor, unblinding_factor) = loop {
+        let trial_factor = rng.gen_biguint_range(&BigUint::from(2u32), &public_key.n);
+
+        if let Ok(inverse) = number::invmod(&trial_factor, &public_key.n) {
+            break (trial_factor, inverse);
+        }
+    };
+
+    // C' = S^e * C mod n decrypts to P' = S * P mod n, which the oracle hasn't seen before
+    let blinded_ciphertext = (public_key.encrypt(&blinding_factor) * ciphertext) % &public_key.n;
+    let blinded_plaintext = oracle_fn(&blinded_ciphertext)?;
+
+    Ok((blinded_plaintext * unblinding_factor) % &public_key.n)
+}
+
+pub fn break_dsa_nonce_range(
+    public_key: &dsa::PublicKey,
+    hash: &BigUint,
+    signature: &dsa::Signature,
+    max_nonce: u64,
+) -> Result<BigUint> {
+    let dsa::DsaParameters {
+        ref g,
+        ref p,
+        ref q,
+    } = public_key.parameters;
+
+    // Step g^k forward one multiplication at a time rather than a full modpow per nonce
+    let mut g_k = BigUint::one();
+
+    for k in 1..max_nonce {
+        g_k = (g_k * g) % p;
+
+        if &g_k % q == signature.r {
+            let x =
+                dsa::private_key_from_nonce(&public_key.parameters, hash, signature, &k.into())?;
+
+            if g.modpow(&x, p) == public_key.y {
+                return Ok(x);
+            }
+        }
+    }
+
+    bail!("no nonce found in range")
+}
+
+pub fn break_dsa_repeated_nonce(
+    public_key: &dsa::PublicKey,
+    signed_messages: &[dsa::SignedMessage],
+) -> Result<DsaKeyState> {
+    let dsa::DsaParameters {
+        ref g,
+        ref p,
+        ref q,
+    } = public_key.parameters;
+
+    let mut colliding_pairs = Vec::new();
+    let mut recovered_key = None;
+
+    for (index_1, message_1) in signed_messages.iter().enumerate() {
+        for (index_2, message_2) in signed_messages.iter().enumerate().skip(index_1 + 1) {
+            // Signatures sharing r were made with the same nonce
+            if message_1.signature.r != message_2.signature.r {
+                continue;
+            }
+
+            // k = (m1 - m2) / (s1 - s2) mod q
+            let hash_difference = (&message_1.hash + q - (&message_2.hash % q)) % q;
+            let s_difference = (&message_1.signature.s + q - &message_2.signature.s) % q;
+            let s_difference_inverse = match number::invmod(&s_difference, q) {
+                Ok(inverse) => inverse,
+                Err(_) => continue,
+            };
+            let k = (hash_difference * s_difference_inverse) % q;
+
+            let x = dsa::private_key_from_nonce(
+                &public_key.parameters,
+                &message_1.hash,
+                &message_1.signature,
+                &k,
+            )?;
+
+            if g.modpow(&x, p) == public_key.y {
+                colliding_pairs.push((index_1, index_2));
+                recovered_key = Some(x);
+            }
+        }
+    }
+
+    match recovered_key {
+        Some(x) => Ok(DsaKeyState { colliding_pairs, x }),
+        None => bail!("no repeated nonce found"),
+    }
+}
+
+pub fn break_rsa_parity_fn<F>(
+    ciphertext: &BigUint,
+    public_key: &rsa::PublicKey,
+    oracle_fn: &mut F,
+    mut progress_fn: Option<&mut dyn FnMut(&BigUint)>,
+) -> Result<BigUint>
+where
+    F: FnMut(&BigUint) -> Result<bool>,
+{
+    let doubler = public_key.encrypt(&BigUint::from(2u32));
+    let mut trial_ciphertext = ciphertext.clone();
+
+    let mut lower_bound = BigRational::zero();
+    let mut upper_bound = BigRational::from_integer(BigInt::from(public_key.n.clone()));
+
+    for _ in 0..public_key.n.bits() {
+        // Doubling the plaintext wraps around n, leaving an odd result, exactly when it was
+        // in the upper half of the current bounds
+        trial_ciphertext = (trial_ciphertext * &doubler) % &public_key.n;
+        let midpoint = (&lower_bound + &upper_bound) / BigRational::from_integer(BigInt::from(2));
+
+        match oracle_fn(&trial_ciphertext)? {
+            true => upper_bound = midpoint,
+            false => lower_bound = midpoint,
+        }
+
+        if let Some(ref mut progress_fn) = progress_fn {
+            progress_fn(&rational_to_biguint(&upper_bound.floor())?);
+        }
+    }
+
+    // The plaintext is the only integer left in [lower_bound, upper_bound)
+    rational_to_biguint(&lower_bound.ceil())
+}
+
+fn rational_to_biguint(value: &BigRational) -> Result<BigUint> {
+    bigint_to_biguint(&value.to_integer())
+}
+
+pub fn break_rsa_padding_fn<F>(
+    ciphertext: &BigUint,
+    public_key: &rsa::PublicKey,
+    oracle_fn: &mut F,
+) -> Result<(BigUint, usize)>
+where
+    F: FnMut(&BigUint) -> Result<bool>,
+{
+    let n = BigInt::from(public_key.n.clone());
+    let big_b = BigInt::one() << (8 * (public_key.byte_len() - 2));
+    let two_b = &big_b * 2u32;
+    let three_b = &big_b * 3u32;
+
+    let mut queries = 0;
+    let mut query = |s: &BigInt| -> Result<bool> {
+        queries += 1;
+        let s = bigint_to_biguint(s)?;
+        oracle_fn(&((ciphertext * public_key.encrypt(&s)) % &public_key.n))
+    };
+
+    // Step 1: the ciphertext is already PKCS conforming, so s0 = 1
+    if !query(&BigInt::one())? {
+        bail!("ciphertext is not PKCS conforming")
+    }
+
+    let mut intervals = vec![(two_b.clone(), &three_b - 1u32)];
+    let mut s = BigInt::zero();
+
+    loop {
+        if s.is_zero() {
+            // Step 2a: find the smallest s1 >= n / 3B that's conforming
+            s = ceil_div(&n, &three_b);
+            while !query(&s)? {
+                s += 1u32;
+            }
+        } else if intervals.len() > 1 {
+            // Step 2b: with several intervals left, step s up one at a time
+            s += 1u32;
+            while !query(&s)? {
+                s += 1u32;
+            }
+        } else {
+            // Step 2c: with one interval left, search r and s together to halve it quickly
+            let (a, b) = (&intervals[0].0, &intervals[0].1);
+            let mut r = ceil_div(&((b * &s - &two_b) * 2u32), &n);
+
+            s = 'search: loop {
+                let s_low = ceil_div(&(&two_b + &r * &n), b);
+                let s_high = ceil_div(&(&three_b + &r * &n), a);
+                let mut trial_s = s_low;
+
+                while trial_s < s_high {
+                    if query(&trial_s)? {
+                        break 'search trial_s;
+                    }
+                    trial_s += 1u32;
+                }
+
+                r += 1u32;
+            };
+        }
+
+        // Step 3: narrow every interval down to the plaintexts consistent with s
+        let mut next_intervals: Vec<(BigInt, BigInt)> = Vec::new();
+
+        for (a, b) in &intervals {
+            let mut r = ceil_div(&(a * &s - &three_b + 1u32), &n);
+            let r_high = (b * &s - &two_b).div_floor(&n);
+
+            while r <= r_high {
+                let low = ceil_div(&(&two_b + &r * &n), &s).max(a.clone());
+                let high = ((&three_b - 1u32 + &r * &n).div_floor(&s)).min(b.clone());
+
+                if low <= high {
+                    merge_interval(&mut next_intervals, low, high);
+                }
+
+                r += 1u32;
+            }
+        }
+
+        if next_intervals.is_empty() {
+            bail!("no intervals left to search")
+        }
+
+        intervals = next_intervals;
+
+        // Step 4: done once a single interval has narrowed to one value
+        if intervals.len() == 1 && intervals[0].0 == intervals[0].1 {
+            let plaintext = bigint_to_biguint(&intervals[0].0)?;
+            return Ok((plaintext, queries));
+        }
+    }
+}
+
+// The new interval can bridge several existing ones, so every interval it overlaps is
+// absorbed into it
+fn merge_interval(intervals: &mut Vec<(BigInt, BigInt)>, mut low: BigInt, mut high: BigInt) {
+    let mut disjoint = Vec::with_capacity(intervals.len() + 1);
+
+    for (interval_low, interval_high) in intervals.drain(..) {
+        if low <= interval_high && interval_low <= high {
+            low = low.min(interval_low);
+            high = high.max(interval_high);
+        } else {
+            disjoint.push((interval_low, interval_high));
+        }
+    }
+
+    disjoint.push((low, high));
+    *intervals = disjoint;
+}
+
+fn ceil_div(numerator: &BigInt, denominator: &BigInt) -> BigInt {
+    -((-numerator).div_floor(denominator))
+}
+
+fn bigint_to_biguint(value: &BigInt) -> Result<BigUint> {
+    match value.to_biguint() {
+        Some(integer) => Ok(integer),
+        None => bail!("integer value is negative"),
+    }
+}
+
+pub fn break_compression_oracle_fn<F>(
+    known_prefix: &[u8],
+    alphabet: &[u8],
+    terminator: u8,
+    max_padding: usize,
+    oracle_fn: &mut F,
+) -> Result<Vec<u8>>
+where
+    F: FnMut(&[u8]) -> Result<usize>,
+{
+    let mut recovered = Vec::new();
+    let mut symbols = alphabet.to_vec();
+    symbols.push(terminator);
+
+    loop {
+        let mut guesses: Vec<Vec<u8>> = symbols.iter().map(|&symbol| vec![symbol]).collect();
+
+        for _ in 0..4 {
+            guesses = narrow_compression_guesses(
+                known_prefix,
+                &recovered,
+                guesses,
+                max_padding,
+                oracle_fn,
+            )?;
+
+            if guesses.len() == 1 {
+                break;
+            }
+
+            // Some wrong guesses compress to exactly as many bits as the right one, so
+            // look one byte further ahead to break the tie
+            guesses = guesses
+                .into_iter()
+                .flat_map(|guess| {
+                    symbols.iter().map(move |&symbol| {
+                        let mut extended = guess.clone();
+                        extended.push(symbol);
+                        extended
+                    })
+                })
+                .collect();
+        }
+
+        if guesses.len() != 1 {
+            bail!("no single guess compressed best")
+        }
+
+        for byte in guesses.remove(0) {
+            if byte == terminator {
+                return Ok(recovered);
+            }
+
+            recovered.push(byte);
+        }
+    }
+}
+
+fn narrow_compression_guesses<F>(
+    known_prefix: &[u8],
+    recovered: &[u8],
+    mut guesses: Vec<Vec<u8>>,
+    max_padding: usize,
+    oracle_fn: &mut F,
+) -> Result<Vec<Vec<u8>>>
+where
+    F: FnMut(&[u8]) -> Result<usize>,
+{
+    // Lengths only move in whole bytes, or whole blocks for CBC, so lead with an
+    // incompressible pad of increasing length and keep whichever guesses compress best
+    for padding_len in 0..max_padding + 1 {
+        let padding: Vec<u8> = (0..padding_len).map(|index| 0x80 + index as u8).collect();
+        let mut lengths = Vec::with_capacity(guesses.len());
+
+        for guess in guesses {
+            let mut trial = padding.clone();
+            trial.extend_from_slice(known_prefix);
+            trial.extend_from_slice(recovered);
+            trial.extend_from_slice(&guess);
+
+            lengths.push((oracle_fn(&trial)?, guess));
+        }
+
+        let shortest = lengths.iter().map(|&(length, _)| length).min();
+        guesses = lengths
+            .into_iter()
+            .filter(|&(length, _)| Some(length) == shortest)
+            .map(|(_, guess)| guess)
+            .collect();
+
+        if guesses.len() == 1 {
+            break;
+        }
+    }
+
+    Ok(guesses)
+}
+
+pub fn break_rc4_bias_fn<F>(samples: usize, threads: usize, oracle_fn: &F) -> Result<Vec<u8>>
+where
+    F: Fn(&[u8]) -> Result<Vec<u8>> + Sync,
+{
+    // Keystream byte indexes and the values they lean towards, strongest first. Z2 is 0
+    // twice as often as it should be, while Z16 and Z32 are only slightly biased
+    let biases = [(1, 0u8), (15, 240), (31, 224)];
+    let cookie_len = oracle_fn(&[])?.len();
+
+    if cookie_len > 32 {
+        bail!("cookie too long to line up with the keystream biases")
+    }
+
+    let mut cookie = vec![0; cookie_len];
+    let mut strongest: Vec<Option<usize>> = vec![None; cookie_len];
+
+    // A prefix of length p lines cookie byte i - p up with keystream byte i, so the most
+    // common ciphertext byte there gives it away. Each cookie byte is taken from the
+    // strongest bias that reaches it
+    let improves = |prefix_len: usize, rank: usize, strongest: &[Option<usize>]| {
+        let position = biases[rank].0;
+
+        position >= prefix_len
+            && position - prefix_len < cookie_len
+            && strongest[position - prefix_len].is_none_or(|best| rank < best)
+    };
+
+    for prefix_len in 0..16 {
+        if !(0..biases.len()).any(|rank| improves(prefix_len, rank, &strongest)) {
+            continue;
+        }
+
+        let positions: Vec<usize> = biases.iter().map(|&(position, _)| position).collect();
+        let counts = count_rc4_ciphertext_bytes(
+            &vec![b'A'; prefix_len],
+            &positions,
+            samples,
+            threads,
+            oracle_fn,
+        )?;
+
+        for (rank, (position_counts, &(position, bias))) in
+            counts.iter().zip(biases.iter()).enumerate()
+        {
+            if !improves(prefix_len, rank, &strongest) {
+                continue;
+            }
+
+            let most_common = (0..256)
+                .max_by_key(|&byte| position_counts[byte])
+                .unwrap_or(0) as u8;
+
+            cookie[position - prefix_len] = most_common ^ bias;
+            strongest[position - prefix_len] = Some(rank);
+        }
+    }
+
+    Ok(cookie)
+}
+
+fn count_rc4_ciphertext_bytes<F>(
+    prefix: &[u8],
+    positions: &[usize],
+    samples: usize,
+    threads: usize,
+    oracle_fn: &F,
+) -> Result<Vec<[usize; 256]>>
+where
+    F: Fn(&[u8]) -> Result<Vec<u8>> + Sync,
+{
+    let threads = threads.max(1);
+
+    let thread_counts: Vec<Result<Vec<[usize; 256]>>> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|thread_index| {
+                let thread_samples =
+                    samples / threads + usize::from(thread_index < samples % threads);
+
+                scope.spawn(move || {
+                    let mut counts = vec![[0; 256]; positions.len()];
+
+                    for _ in 0..thread_samples {
+                        let ciphertext = oracle_fn(prefix)?;
+
+                        for (position_counts, &position) in counts.iter_mut().zip(positions) {
+                            if let Some(&byte) = ciphertext.get(position) {
+                                position_counts[byte as usize] += 1;
+                            }
+                        }
+                    }
+
+                    Ok(counts)
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| match handle.join() {
+                Ok(counts) => counts,
+                Err(_) => bail!("counting thread panicked"),
+            })
+            .collect()
+    });
+
+    let mut counts = vec![[0; 256]; positions.len()];
+
+    for thread_count in thread_counts {
+        for (position, position_counts) in thread_count?.iter().enumerate() {
+            for (byte, count) in position_counts.iter().enumerate() {
+                counts[position][byte] += count;
+            }
+        }
+    }
+
+    Ok(counts)
+}
+
+pub fn break_dh_small_subgroup_fn<F>(
+    parameters: &DhParameters,
+    factor_bound: u64,
+    oracle_fn: &mut F,
+) -> Result<(BigUint, BigUint)>
+where
+    F: FnMut(&BigUint) -> Result<(Vec<u8>, Vec<u8>)>,
+{
+    let DhParameters { ref p, ref q, .. } = *parameters;
+    let cofactor = (p - 1u32) / q;
+    let mut residues = Vec::new();
+    let mut modulus = BigUint::one();
+
+    // An element of order r confines the shared secret to r values, so the MAC gives
+    // away the private key mod r
+    for r in number::small_factors(&cofactor, factor_bound) {
+        let h = dh::element_of_order(p, &r)?;
+        let (message, mac) = oracle_fn(&h)?;
+        let mut shared_secret = BigUint::one();
+        let mut residue = None;
+
+        for b in 0..r.to_u64().unwrap_or(0) {
+            if dh::mac(&shared_secret, &message)? == mac {
+                residue = Some(BigUint::from(b));
+                break;
+            }
+
+            shared_secret = (shared_secret * &h) % p;
+        }
+
+        match residue {
+            Some(residue) => residues.push((residue, r.clone())),
+            None => bail!("no residue matched the MAC"),
+        }
+
+        modulus *= &r;
+
+        if &modulus > q {
+            break;
+        }
+    }
+
+    number::crt(&residues)
+}
+
+pub fn break_dh_subgroup_kangaroo_fn<F>(
+    parameters: &DhParameters,
+    public_key: &BigUint,
+    factor_bound: u64,
+    kangaroo_parameters: Option<KangarooParameters>,
+    oracle_fn: &mut F,
+) -> Result<BigUint>
+where
+    F: FnMut(&BigUint) -> Result<(Vec<u8>, Vec<u8>)>,
+{
+    let DhParameters {
+        ref g,
+        ref p,
+        ref q,
+    } = *parameters;
+    let (residue, modulus) = break_dh_small_subgroup_fn(parameters, factor_bound, oracle_fn)?;
+
+    if &modulus > q {
+        return Ok(residue);
+    }
+
+    // With x = n + m * r, y * g^-n = (g^r)^m and m is somewhere in [0, (q - 1) / r]
+    let group = MultiplicativeGroup::new(p);
+    let generator = g.modpow(&modulus, p);
+    let y = (public_key * g.modpow(&(q - &residue), p)) % p;
+    let upper = (q - 1u32) / &modulus;
+    let kangaroo_parameters =
+        kangaroo_parameters.unwrap_or_else(|| KangarooParameters::for_interval(&upper));
+
+    match dlog::kangaroo_with_retries(
+        &group,
+        &generator,
+        &y,
+        &BigUint::zero(),
+        &upper,
+        &kangaroo_parameters,
+    )? {
+        Some(m) => Ok(residue + m * modulus),
+        None => bail!("wild kangaroo never landed in the trap"),
+    }
+}
+
+// invalid_curves holds (b, curve order) pairs for curves sharing a and p with the real one
+pub fn break_ecdh_invalid_curve_fn<F>(
+    parameters: &EcdhParameters,
+    invalid_curves: &[(BigUint, BigUint)],
+    factor_bound: u64,
+    oracle_fn: &mut F,
+) -> Result<(BigUint, BigUint)>
+where
+    F: FnMut(&Point) -> Result<(Vec<u8>, Vec<u8>)>,
+{
+    let p = &parameters.curve.p;
+    let mut residues: Vec<(BigUint, BigUint)> = Vec::new();
+    let mut modulus = BigUint::one();
+
+    // Scalar multiplication never touches b, so Bob happily multiplies a point from some
+    // other curve and his MAC gives away the private key mod that point's small order
+    'curves: for (b, order) in invalid_curves {
+        let curve = parameters.curve.with_b(b.clone())?;
+
+        for r in number::small_factors(order, factor_bound) {
+            if residues.iter().any(|(_, modulus)| *modulus == r) {
+                continue;
+            }
+
+            let h = curve.point_of_order(order, &r)?;
+            let (message, mac) = oracle_fn(&h)?;
+            let mut shared_secret = Point::Infinity;
+            let mut residue = None;
+
+            for k in 0..r.to_u64().unwrap_or(0) {
+                if ecdh::mac(&shared_secret, p, &message)? == mac {
+                    residue = Some(BigUint::from(k));
+                    break;
+                }
+
+                shared_secret = curve.add(&shared_secret, &h);
+            }
+
+            match residue {
+                Some(residue) => residues.push((residue, r.clone())),
+                None => bail!("no residue matched the MAC"),
+            }
+
+            modulus *= &r;
+
+            if modulus > parameters.n {
+                break 'curves;
+            }
+        }
+    }
+
+    number::crt(&residues)
+}
+
+pub fn break_ecdh_twist_fn<F>(
+    parameters: &LadderEcdhParameters,
+    public_key: &BigUint,
+    twist_order: &BigUint,
+    private_key_bound: &BigUint,
+    factor_bound: u64,
+    kangaroo_parameters: Option<KangarooParameters>,
+    oracle_fn: &mut F,
+) -> Result<BigUint>
+where
+    F: FnMut(&BigUint) -> Result<(Vec<u8>, Vec<u8>)>,
+{
+    let curve = &parameters.curve;
+    let twist = curve.twist()?;
+    let twist_weierstrass = twist.to_weierstrass()?;
+    let twist_u = |point: &Point| {
+        twist
+            .point_from_weierstrass(point)
+            .map_or_else(BigUint::zero, |(u, _)| u)
+    };
+    let mut residue = BigUint::zero();
+    let mut modulus = BigUint::one();
+    let mut combined = Point::Infinity;
+
+    // Bob's ladder never checks that u is on his curve, so u coordinates from the twist
+    // leak the private key mod the twist's small subgroup orders. The ladder collapses
+    // the 2-torsion to u = 0, so only odd orders are any use
+    for r in number::small_factors(twist_order, factor_bound) {
+        if r.is_even() {
+            continue;
+        }
+
+        let h = twist_weierstrass.point_of_order(twist_order, &r)?;
+        let (message, mac) = oracle_fn(&twist_u(&h))?;
+        let mut shared_secret = Point::Infinity;
+        let mut candidate = None;
+
+        // k and r - k give the same u, so only half the subgroup needs searching
+        for k in 0..=r.to_u64().unwrap_or(0) / 2 {
+            if dh::mac(&twist_u(&shared_secret), &message)? == mac {
+                candidate = Some(BigUint::from(k));
+                break;
+            }
+
+            shared_secret = twist_weierstrass.add(&shared_secret, &h);
+        }
+
+        let candidate = match candidate {
+            Some(candidate) => candidate,
+            None => bail!("no residue matched the MAC"),
+        };
+
+        // The private key is ±residue mod the running modulus and ±candidate mod r, which
+        // leaves two consistent pairs. A point of order modulus * r tells them apart
+        let (first, _) = number::crt(&[
+            (residue.clone(), modulus.clone()),
+            (candidate.clone(), r.clone()),
+        ])?;
+        let (second, _) = number::crt(&[
+            (residue, modulus.clone()),
+            ((&r - candidate) % &r, r.clone()),
+        ])?;
+        combined = twist_weierstrass.add(&combined, &h);
+
+        residue = if modulus.is_one() || first == second {
+            first
+        } else {
+            let (message, mac) = oracle_fn(&twist_u(&combined))?;
+            let shared_secret = twist_weierstrass.scale(&combined, &first);
+
+            match dh::mac(&twist_u(&shared_secret), &message)? == mac {
+                true => first,
+                false => second,
+            }
+        };
+        modulus *= &r;
+    }
+
+    // What remains is x = ±residue + m * modulus, and since Bob's public key is only a u
+    // coordinate his public point is known up to sign as well
+    let weierstrass = curve.to_weierstrass()?;
+    let g = weierstrass_point(curve, &parameters.u)?;
+    let y = weierstrass_point(curve, public_key)?;
+    let generator = weierstrass.scale(&g, &modulus);
+    let upper = private_key_bound / &modulus;
+    let mut kangaroo_parameters =
+        kangaroo_parameters.unwrap_or_else(|| KangarooParameters::for_interval(&upper));
+    let residues = [residue.clone(), (&modulus - &residue) % &modulus];
+
+    // Three of the four candidates have no solution in the interval, so each attempt tries
+    // them all before perturbing the walk for the next
+    for _ in 0..dlog::KANGAROO_ATTEMPTS {
+        for residue in &residues {
+            let offset = weierstrass.negate(&weierstrass.scale(&g, residue));
+
+            for target in &[y.clone(), weierstrass.negate(&y)] {
+                let shifted = weierstrass.add(target, &offset);
+                let m = dlog::kangaroo(
+                    &weierstrass,
+                    &generator,
+                    &shifted,
+                    &BigUint::zero(),
+                    &upper,
+                    &kangaroo_parameters,
+                )?;
+
+                if let Some(m) = m {
+                    return Ok(residue + m * &modulus);
+                }
+            }
+        }
+
+        kangaroo_parameters = kangaroo_parameters.perturbed();
+    }
+
+    bail!("wild kangaroo never landed in the trap")
+}
+
+fn weierstrass_point(curve: &MontgomeryCurve, u: &BigUint) -> Result<Point> {
+    match curve.weierstrass_point(u) {
+        Some(point) => Ok(point),
+        None => bail!("u coordinate is not on the curve"),
+    }
+}
+
+// Each nonce is k = 2^l * b with b < n / 2^l, so b = t * d + u mod n is small for
+// t = r / (s * 2^l) and u = H(m) / (s * 2^l). That is a hidden number problem, and the
+// private key falls out of a short vector in the lattice spanned by n * e_i and the
+// t and u rows
+pub fn break_ecdsa_biased_nonce(
+    public_key: &ecdsa::PublicKey,
+    signed_messages: &[(Vec<u8>, Signature)],
+    zero_bits: usize,
+) -> Result<BigUint> {
+    let EcdsaParameters {
+        ref curve,
+        ref g,
+        ref n,
+    } = public_key.parameters;
+    let count = signed_messages.len();
+    let scale = BigUint::one() << zero_bits;
+    let scale_inverse = number::invmod(&(&scale % n), n)?;
+    let rational = |value: &BigUint| BigRational::from_integer(BigInt::from(value.clone()));
+    let mut basis = vec![vec![BigRational::zero(); count + 2]; count + 2];
+
+    for (i, (message, signature)) in signed_messages.iter().enumerate() {
+        let s_inverse = number::invmod(&signature.s, n)?;
+        let t = (&signature.r * &s_inverse * &scale_inverse) % n;
+        let u = (ecdsa::message_hash(message, n) * &s_inverse * &scale_inverse) % n;
+
+        basis[i][i] = rational(n);
+        basis[count][i] = rational(&t);
+        basis[count + 1][i] = rational(&u);
+    }
+
+    let scale = rational(&scale);
+    let ct = BigRational::one() / &scale;
+    let cu = rational(n) / &scale;
+    basis[count][count] = ct;
+    basis[count + 1][count + 1] = cu.clone();
+
+    let delta = BigRational::new(BigInt::from(99), BigInt::from(100));
+    let n = BigInt::from(n.clone());
+
+    // The short vector is d * t_row + u_row + (multiples of n * e_i), which ends in
+    // (d / 2^l, n / 2^l), or its negation
+    for row in lattice::lll(&basis, &delta)? {
+        let d = if row[count + 1] == cu {
+            (&row[count] * &scale).to_integer()
+        } else if row[count + 1] == -&cu {
+            -(&row[count] * &scale).to_integer()
+        } else {
+            continue;
+        };
+        let d = bigint_to_biguint(&d.mod_floor(&n))?;
+
+        if curve.scale(g, &d) == public_key.q {
+            return Ok(d);
+        }
+    }
+
+    bail!("no short vector gave the private key")
+}
+
+// Under a repeated nonce every tag is GHASH_H plus the same mask, so adding the tag
+// polynomials of two messages cancels the mask and leaves a polynomial vanishing at H.
+// Each further message pairs up with the first to whittle the candidate roots down
+pub fn break_gcm_nonce_reuse(messages: &[GcmMessage]) -> Result<Element> {
+    if messages.len() < 2 {
+        bail!("at least two messages are needed")
+    }
+
+    let first = gcm_tag_polynomial(&messages[0])?;
+    let mut candidates: Option<Vec<Element>> = None;
+
+    for message in &messages[1..] {
+        let difference = &first + &gcm_tag_polynomial(message)?;
+
+        if difference.is_zero() {
+            continue;
+        }
+
+        let roots = polynomial::roots(&difference)?;
+
+        candidates = Some(match candidates {
+            Some(candidates) => candidates
+                .into_iter()
+                .filter(|candidate| roots.contains(candidate))
+                .collect(),
+            None => roots,
+        });
+    }
+
+    match candidates {
+        Some(ref candidates) if candidates.len() == 1 => Ok(candidates[0]),
+        Some(ref candidates) if candidates.is_empty() => bail!("no authentication key fits"),
+        _ => bail!("could not isolate the authentication key"),
+    }
+}
+
+// The tag plus GHASH written out in powers of H: the last block goes with H, the one
+// before it with H^2, and so on
+fn gcm_tag_polynomial(message: &GcmMessage) -> Result<Polynomial> {
+    let mut coefficients = vec![Element::from_bytes(&message.tag)?];
+    coefficients.extend(
+        gf128::ghash_blocks(&message.aad, &message.ciphertext)?
+            .into_iter()
+            .rev(),
+    );
+
+    Ok(Polynomial::new(coefficients))
+}
+
+// Ferguson's attack on truncated GCM tags. Changing only the ciphertext blocks that GHASH
+// multiplies by h^(2^i) moves the tag by the sum of d_i * h^(2^i), and since squaring is
+// linear that is Ad * h for the GF(2) matrix Ad, the sum of M(d_i) * S^i. Drawing the
+// d_i from the kernel of the map onto the first rows of Ad zeroes those tag bits for any
+// h, and each forgery the oracle accepts shows the remaining tag rows of Ad * h vanish
+// too, which are linear equations narrowing down h
+pub fn break_gcm_truncated_mac_fn<F>(message: &GcmMessage, oracle_fn: &mut F) -> Result<Element>
+where
+    F: FnMut(&GcmMessage) -> bool,
+{
+    let length = message.ciphertext.len();
+
+    if aes::padded_len(length, gf128::BLOCK_SIZE) != length {
+        bail!("ciphertext must be a whole number of blocks")
+    }
+
+    // Counting the length block as 1, block 2^i from the end must still be ciphertext
+    let block_exponent = (length / gf128::BLOCK_SIZE + 1).ilog2() as usize;
+
+    if block_exponent == 0 {
+        bail!("ciphertext is too short")
+    }
+
+    recover_gcm_key_from_forgeries(message, Element::zero(), block_exponent, oracle_fn)
+}
+
+// The tag of an empty message lets us forge ciphertexts of any length instead of being
+// limited by the one we were given. An all zero ciphertext of 2^n - 1 blocks hashes to
+// L * h for its length block L, still linear in h, so forgeries now solve for the first
+// rows of Ad matching those of M(L) rather than just landing in a kernel
+pub fn break_gcm_truncated_mac_extension_fn<F>(
+    message: &GcmMessage,
+    block_exponent: usize,
+    oracle_fn: &mut F,
+) -> Result<Element>
+where
+    F: FnMut(&GcmMessage) -> bool,
+{
+    if !message.aad.is_empty() || !message.ciphertext.is_empty() {
+        bail!("the message must be empty")
+    }
+
+    if block_exponent == 0 {
+        bail!("the extended ciphertext needs at least one block")
+    }
+
+    let extended = GcmMessage {
+        aad: Vec::new(),
+        ciphertext: vec![0; ((1 << block_exponent) - 1) * gf128::BLOCK_SIZE],
+        tag: message.tag.clone(),
+    };
+    let length_block = *gf128::ghash_blocks(&extended.aad, &extended.ciphertext)?
+        .last()
+        .ok_or("no length block")?;
+
+    recover_gcm_key_from_forgeries(&extended, length_block, block_exponent, oracle_fn)
+}
+
+// Forgeries perturb the ciphertext of base, whose GHASH differs from that of the message
+// its tag came from by offset * h. The bits of h are tracked as basis * h' for the h' not
+// yet pinned down, so every step solves only for what is left
+fn recover_gcm_key_from_forgeries<F>(
+    base: &GcmMessage,
+    offset: Element,
+    block_exponent: usize,
+    oracle_fn: &mut F,
+) -> Result<Element>
+where
+    F: FnMut(&GcmMessage) -> bool,
+{
+    let mut rng = rand::thread_rng();
+    let tag_bits = base.tag.len() * 8;
+    let variable_count = block_exponent * 128;
+    let block_count = base.ciphertext.len() / gf128::BLOCK_SIZE;
+    let offset_matrix = gf128::multiplication_matrix(offset)?;
+    let squaring = gf128::squaring_matrix()?;
+    let monomial_matrices = (0..128)
+        .map(|j| gf128::multiplication_matrix(Element(1 << j)))
+        .collect::<Result<Vec<Matrix>>>()?;
+    let mut squaring_powers = vec![squaring.clone()];
+
+    while squaring_powers.len() < block_exponent {
+        let next = squaring_powers[squaring_powers.len() - 1].multiply(&squaring)?;
+        squaring_powers.push(next);
+    }
+
+    let mut forged = GcmMessage {
+        aad: base.aad.clone(),
+        ciphertext: base.ciphertext.clone(),
+        tag: base.tag.clone(),
+    };
+    let mut basis = Matrix::identity(128);
+
+    while basis.column_count > 1 {
+        let dimension = basis.column_count;
+        let shifted_bases = squaring_powers
+            .iter()
+            .map(|power| power.multiply(&basis))
+            .collect::<Result<Vec<Matrix>>>()?;
+        let shifted_offset = offset_matrix.multiply(&basis)?;
+
+        // As many rows as the variables can zero, keeping at least one for new equations
+        let mut zero_rows = cmp::min(tag_bits - 1, (variable_count - 1) / dimension);
+        let (system, solution) = loop {
+            let (system, target) = gcm_forgery_system(
+                zero_rows,
+                &shifted_bases,
+                &shifted_offset,
+                &monomial_matrices,
+            )?;
+
+            match system.solve(&target)? {
+                Some(solution) => break (system, solution),
+                None => zero_rows -= 1,
+            }
+        };
+        let kernel = system.kernel();
+        let particular = Matrix::from_rows(&[solution])?.rows.remove(0);
+
+        loop {
+            let mut variables = particular.clone();
+
+            for row in &kernel.rows {
+                if rng.gen() {
+                    for (word, &kernel_word) in variables.iter_mut().zip(row) {
+                        *word ^= kernel_word;
+                    }
+                }
+            }
+
+            if offset.is_zero() && variables.iter().all(|&word| word == 0) {
+                continue;
+            }
+
+            let differences: Vec<Element> = variables
+                .chunks(2)
+                .map(|words| Element(u128::from(words[0]) | (u128::from(words[1]) << 64)))
+                .collect();
+
+            apply_gcm_differences(&mut forged.ciphertext, block_count, &differences);
+            let accepted = oracle_fn(&forged);
+            apply_gcm_differences(&mut forged.ciphertext, block_count, &differences);
+
+            if !accepted {
+                continue;
+            }
+
+            let mut error = shifted_offset.clone();
+
+            for (difference, shifted_basis) in differences.iter().zip(&shifted_bases) {
+                error =
+                    &error + &gf128::multiplication_matrix(*difference)?.multiply(shifted_basis)?;
+            }
+
+            let solutions = error.row_range(zero_rows, tag_bits).kernel();
+
+            if solutions.row_count == 0 {
+                bail!("no authentication key fits the accepted forgery")
+            }
+
+            if solutions.row_count < dimension {
+                basis = basis.multiply(&solutions.transpose())?;
+                break;
+            }
+        }
+    }
+
+    Element::from_bits(&basis.column(0))
+}
+
+// The linear system in the bits of the d_i whose solutions zero the first zero_rows rows
+// of (Ad + M(offset)) * basis. Row a * dimension + c of the system is entry (a, c), and
+// the column for bit j of d_i is that entry of M(x^j) * S^i * basis
+fn gcm_forgery_system(
+    zero_rows: usize,
+    shifted_bases: &[Matrix],
+    shifted_offset: &Matrix,
+    monomial_matrices: &[Matrix],
+) -> Result<(Matrix, Vec<bool>)> {
+    let dimension = shifted_offset.column_count;
+    let mut system = Matrix::zero(zero_rows * dimension, shifted_bases.len() * 128);
+    let mut target = vec![false; zero_rows * dimension];
+
+    for (i, shifted_basis) in shifted_bases.iter().enumerate() {
+        for (j, monomial_matrix) in monomial_matrices.iter().enumerate() {
+            let entries = monomial_matrix
+                .row_range(0, zero_rows)
+                .multiply(shifted_basis)?;
+
+            for a in 0..zero_rows {
+                for c in 0..dimension {
+                    if entries.get(a, c) {
+                        system.set(a * dimension + c, i * 128 + j, true);
+                    }
+                }
+            }
+        }
+    }
+
+    for a in 0..zero_rows {
+        for c in 0..dimension {
+            target[a * dimension + c] = shifted_offset.get(a, c);
+        }
+    }
+
+    Ok((system, target))
+}
+
+// d_i goes on the block GHASH multiplies by h^(2^i), with the length block counted as 1
+fn apply_gcm_differences(ciphertext: &mut [u8], block_count: usize, differences: &[Element]) {
+    for (i, difference) in differences.iter().enumerate() {
+        let start = (block_count + 1 - (1 << (i + 1))) * gf128::BLOCK_SIZE;
+
+        for (byte, difference_byte) in ciphertext[start..start + gf128::BLOCK_SIZE]
+            .iter_mut()
+            .zip(difference.to_bytes())
+        {
+            *byte ^= difference_byte;
+        }
+    }
+}
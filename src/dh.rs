@@ -0,0 +1,86 @@
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{Num, One};
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+use rand;
+
+use utility::error::{Result, ResultExt};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DhParameters {
+    pub g: BigUint,
+    pub p: BigUint,
+    pub q: BigUint,
+}
+
+pub struct Dh {
+    pub parameters: DhParameters,
+    pub x: BigUint,
+    pub y: BigUint,
+}
+
+impl DhParameters {
+    pub fn new(g: BigUint, p: BigUint, q: BigUint) -> Self {
+        DhParameters { g, p, q }
+    }
+
+    pub fn from_decimal(g: &str, p: &str, q: &str) -> Result<Self> {
+        Ok(DhParameters {
+            g: BigUint::from_str_radix(g, 10).chain_err(|| "could not parse g")?,
+            p: BigUint::from_str_radix(p, 10).chain_err(|| "could not parse p")?,
+            q: BigUint::from_str_radix(q, 10).chain_err(|| "could not parse q")?,
+        })
+    }
+
+    pub fn is_valid_public_key(&self, y: &BigUint) -> bool {
+        y > &BigUint::one() && y < &self.p && y.modpow(&self.q, &self.p).is_one()
+    }
+}
+
+impl Dh {
+    pub fn new(parameters: DhParameters) -> Self {
+        let mut rng = rand::thread_rng();
+        let x = rng.gen_biguint_range(&BigUint::one(), &parameters.q);
+        let y = parameters.g.modpow(&x, &parameters.p);
+
+        Dh { parameters, x, y }
+    }
+
+    pub fn shared_secret(&self, public_key: &BigUint) -> BigUint {
+        public_key.modpow(&self.x, &self.parameters.p)
+    }
+}
+
+// A random element of order r, for r a prime factor of p - 1
+pub fn element_of_order(p: &BigUint, r: &BigUint) -> Result<BigUint> {
+    let p_minus_one = p - 1u32;
+
+    if (&p_minus_one % r) != BigUint::from(0u32) {
+        bail!("order does not divide p - 1")
+    }
+
+    let exponent = &p_minus_one / r;
+    let mut rng = rand::thread_rng();
+
+    loop {
+        let h = rng
+            .gen_biguint_range(&BigUint::from(2u32), p)
+            .modpow(&exponent, p);
+
+        if !h.is_one() {
+            return Ok(h);
+        }
+    }
+}
+
+pub fn mac(shared_secret: &BigUint, message: &[u8]) -> Result<Vec<u8>> {
+    let key = PKey::hmac(&shared_secret.to_bytes_be()).chain_err(|| "could not create hmac key")?;
+    let mut signer =
+        Signer::new(MessageDigest::sha256(), &key).chain_err(|| "could not create signer")?;
+    signer
+        .update(message)
+        .chain_err(|| "could not update signer")?;
+
+    signer.sign_to_vec().chain_err(|| "could not sign message")
+}
@@ -0,0 +1,267 @@
+use num_bigint::BigUint;
+use num_traits::{One, Pow, Zero};
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use utility::error::Result;
+use utility::number;
+
+// Each retry doubles the tame kangaroo's run, so this is at most 15 times the first attempt
+pub static KANGAROO_ATTEMPTS: usize = 4;
+
+pub trait CyclicGroup {
+    type Element: Clone + Eq + Hash;
+
+    fn identity(&self) -> Self::Element;
+    fn combine(&self, first: &Self::Element, second: &Self::Element) -> Self::Element;
+    fn scale(&self, element: &Self::Element, scalar: &BigUint) -> Self::Element;
+    // Any cheap, deterministic reduction of an element to an integer, for picking jumps
+    fn fingerprint(&self, element: &Self::Element) -> u64;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultiplicativeGroup {
+    pub p: BigUint,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KangarooParameters {
+    pub jump_table_size: u32,
+    pub tame_jumps: u64,
+    pub fingerprint_offset: u64,
+}
+
+impl MultiplicativeGroup {
+    pub fn new(p: &BigUint) -> Self {
+        MultiplicativeGroup { p: p.clone() }
+    }
+}
+
+impl CyclicGroup for MultiplicativeGroup {
+    type Element = BigUint;
+
+    fn identity(&self) -> BigUint {
+        BigUint::one()
+    }
+
+    fn combine(&self, first: &BigUint, second: &BigUint) -> BigUint {
+        (first * second) % &self.p
+    }
+
+    fn scale(&self, element: &BigUint, scalar: &BigUint) -> BigUint {
+        element.modpow(scalar, &self.p)
+    }
+
+    fn fingerprint(&self, element: &BigUint) -> u64 {
+        element
+            .to_bytes_le()
+            .iter()
+            .take(8)
+            .rev()
+            .fold(0, |fingerprint, &byte| fingerprint << 8 | u64::from(byte))
+    }
+}
+
+impl KangarooParameters {
+    // Jumps of 2^0 .. 2^(k-1) average (2^k - 1) / k, which should be about half the square
+    // root of the interval, and the tame kangaroo makes four times that many jumps
+    pub fn for_interval(width: &BigUint) -> Self {
+        let target = width.sqrt() / 2u32;
+        let mut jump_table_size = 1;
+
+        while BigUint::from(mean_jump(jump_table_size)) < target && jump_table_size < 63 {
+            jump_table_size += 1;
+        }
+
+        KangarooParameters {
+            jump_table_size,
+            tame_jumps: 4 * mean_jump(jump_table_size),
+            fingerprint_offset: 0,
+        }
+    }
+
+    // The walk is deterministic, so a miss would repeat forever. Shifting the fingerprint
+    // sends both kangaroos down different paths, and a longer tame run widens the trap
+    pub fn perturbed(&self) -> Self {
+        KangarooParameters {
+            jump_table_size: self.jump_table_size,
+            tame_jumps: 2 * self.tame_jumps,
+            fingerprint_offset: self.fingerprint_offset + 1,
+        }
+    }
+}
+
+fn mean_jump(jump_table_size: u32) -> u64 {
+    ((1u64 << jump_table_size) - 1) / u64::from(jump_table_size)
+}
+
+// Pollard's lambda method for y = g^x with x in [lower, upper]
+pub fn kangaroo<G: CyclicGroup>(
+    group: &G,
+    generator: &G::Element,
+    y: &G::Element,
+    lower: &BigUint,
+    upper: &BigUint,
+    parameters: &KangarooParameters,
+) -> Result<Option<BigUint>> {
+    if upper < lower || parameters.jump_table_size == 0 || parameters.jump_table_size > 63 {
+        bail!("invalid kangaroo interval or parameters")
+    }
+
+    let jump_table_size = u64::from(parameters.jump_table_size);
+    let jumps: Vec<(u64, G::Element)> = (0..jump_table_size)
+        .map(|index| {
+            let distance = 1u64 << index;
+            (distance, group.scale(generator, &BigUint::from(distance)))
+        })
+        .collect();
+    let jump = |element: &G::Element| {
+        let fingerprint = group
+            .fingerprint(element)
+            .wrapping_add(parameters.fingerprint_offset);
+
+        &jumps[(fingerprint % jump_table_size) as usize]
+    };
+
+    // The tame kangaroo starts at the top of the interval and leaves a trap where it stops
+    let mut tame_distance = BigUint::zero();
+    let mut tame = group.scale(generator, upper);
+
+    for _ in 0..parameters.tame_jumps {
+        let &(distance, ref step) = jump(&tame);
+        tame_distance += distance;
+        tame = group.combine(&tame, step);
+    }
+
+    // The wild kangaroo starts at y and either lands in the trap or runs past it
+    let limit = upper - lower + &tame_distance;
+    let mut wild_distance = BigUint::zero();
+    let mut wild = y.clone();
+
+    while wild_distance <= limit {
+        // Once the kangaroos meet they walk together, so a meeting that only gives x up to
+        // a multiple of the group order is as good as a miss
+        if wild == tame {
+            let x = upper + tame_distance - wild_distance;
+
+            return Ok(if &x <= upper { Some(x) } else { None });
+        }
+
+        let &(distance, ref step) = jump(&wild);
+        wild_distance += distance;
+        wild = group.combine(&wild, step);
+    }
+
+    Ok(None)
+}
+
+// Repeats the walk with perturbed parameters until the wild kangaroo is caught
+pub fn kangaroo_with_retries<G: CyclicGroup>(
+    group: &G,
+    generator: &G::Element,
+    y: &G::Element,
+    lower: &BigUint,
+    upper: &BigUint,
+    parameters: &KangarooParameters,
+) -> Result<Option<BigUint>> {
+    let mut parameters = *parameters;
+
+    for _ in 0..KANGAROO_ATTEMPTS {
+        if let Some(x) = kangaroo(group, generator, y, lower, upper, &parameters)? {
+            return Ok(Some(x));
+        }
+
+        parameters = parameters.perturbed();
+    }
+
+    Ok(None)
+}
+
+// Pohlig-Hellman for y = g^x, where the order of g is the product of the given prime
+// powers. Gives x mod that order, or None if y is not a power of g
+pub fn pohlig_hellman<G: CyclicGroup>(
+    group: &G,
+    generator: &G::Element,
+    y: &G::Element,
+    order_factors: &[(BigUint, u32)],
+) -> Result<Option<BigUint>> {
+    let order: BigUint = order_factors
+        .iter()
+        .map(|(prime, exponent)| prime.pow(*exponent))
+        .product();
+    let mut residues = Vec::new();
+
+    for (prime, exponent) in order_factors {
+        let prime_power = prime.pow(*exponent);
+        let cofactor = &order / &prime_power;
+        let g = group.scale(generator, &cofactor);
+        let h = group.scale(y, &cofactor);
+
+        // Recover x mod prime^exponent one base-prime digit at a time, each digit being a
+        // discrete log in the subgroup of order prime
+        let gamma = group.scale(&g, &prime.pow(exponent - 1));
+        let mut x = BigUint::zero();
+        let mut digit_weight = BigUint::one();
+
+        for k in 0..*exponent {
+            let shift = group.scale(&g, &((&prime_power - &x % &prime_power) % &prime_power));
+            let h_k = group.scale(&group.combine(&shift, &h), &prime.pow(exponent - 1 - k));
+
+            match baby_step_giant_step(group, &gamma, &h_k, prime) {
+                Some(digit) => x += digit * &digit_weight,
+                None => return Ok(None),
+            }
+
+            digit_weight *= prime;
+        }
+
+        residues.push((x, prime_power));
+    }
+
+    let (x, _) = number::crt(&residues)?;
+
+    // Every digit matching is necessary but not sufficient for y to lie in <g>
+    if group.scale(generator, &x) == *y {
+        Ok(Some(x))
+    } else {
+        Ok(None)
+    }
+}
+
+// Shanks' method for y = g^x where g has the given order
+fn baby_step_giant_step<G: CyclicGroup>(
+    group: &G,
+    generator: &G::Element,
+    y: &G::Element,
+    order: &BigUint,
+) -> Option<BigUint> {
+    let steps = order.sqrt() + 1u32;
+    let mut baby_steps = HashMap::new();
+    let mut element = group.identity();
+    let mut j = BigUint::zero();
+
+    while j < steps {
+        baby_steps
+            .entry(element.clone())
+            .or_insert_with(|| j.clone());
+        element = group.combine(&element, generator);
+        j += 1u32;
+    }
+
+    // Giant steps multiply by g^-m, which is g^(order - m)
+    let giant_step = group.scale(generator, &((order - &steps % order) % order));
+    let mut element = y.clone();
+    let mut i = BigUint::zero();
+
+    while i < steps {
+        if let Some(j) = baby_steps.get(&element) {
+            return Some((&i * &steps + j) % order);
+        }
+
+        element = group.combine(&element, &giant_step);
+        i += 1u32;
+    }
+
+    None
+}
@@ -0,0 +1,188 @@
+use hex;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{Num, One, Zero};
+use openssl::sha;
+use rand;
+
+use utility::error::{Result, ResultExt};
+use utility::number;
+
+static P_HEX: &str = "800000000000000089e1855218a0e7dac38136ffafa72eda7\
+                              859f2171e25e65eac698c1702578b07dc2a1076da241c76c6\
+                              2d374d8389ea5aeffd3226a0530cc565f3bf6b50929139ebe\
+                              ac04f48c3c84afb796d61e5a4f9a8fda812ab59494232c7d2\
+                              b4deb50aa18ee9e132bfa85ac4374d7f9091abc3d015efc87\
+                              1a584471bb1";
+static Q_HEX: &str = "f4f47f05794b256174bba6e9b396a7707e563c5b";
+static G_HEX: &str = "5958c9d3898b224b12672c0b98e06c60df923cb8bc999d119\
+                              458fef538b8fa4046c8db53039db620c094c9fa077ef389b5\
+                              322a559946a71903f990f1f7e0e025e2d7f7cf494aff1a047\
+                              0f5b64c36b625a097f1651fe775323556fe00b3608c887892\
+                              878480e99041be601a62166ca6894bdd41a7054ec89f756ba\
+                              9fc95302291";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DsaParameters {
+    pub g: BigUint,
+    pub p: BigUint,
+    pub q: BigUint,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublicKey {
+    pub parameters: DsaParameters,
+    pub y: BigUint,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Signature {
+    pub r: BigUint,
+    pub s: BigUint,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignedMessage {
+    pub hash: BigUint,
+    pub message: String,
+    pub signature: Signature,
+}
+
+pub struct Dsa {
+    pub parameters: DsaParameters,
+    pub x: BigUint,
+    pub y: BigUint,
+}
+
+impl DsaParameters {
+    pub fn new() -> Result<Self> {
+        Ok(DsaParameters {
+            g: BigUint::from_str_radix(G_HEX, 16).chain_err(|| "could not parse g")?,
+            p: BigUint::from_str_radix(P_HEX, 16).chain_err(|| "could not parse p")?,
+            q: BigUint::from_str_radix(Q_HEX, 16).chain_err(|| "could not parse q")?,
+        })
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.g > BigUint::one() && self.g < self.p && self.g.modpow(&self.q, &self.p).is_one()
+    }
+}
+
+impl PublicKey {
+    pub fn with_generator(&self, g: BigUint) -> Self {
+        let mut parameters = self.parameters.clone();
+        parameters.g = g;
+
+        PublicKey {
+            parameters,
+            y: self.y.clone(),
+        }
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
+        let q = &self.parameters.q;
+
+        if !self.parameters.is_valid() {
+            return false;
+        }
+        if signature.r.is_zero() || &signature.r >= q {
+            return false;
+        }
+        if signature.s.is_zero() || &signature.s >= q {
+            return false;
+        }
+
+        self.verify_sloppy(message, signature)
+    }
+
+    pub fn verify_sloppy(&self, message: &[u8], signature: &Signature) -> bool {
+        let DsaParameters {
+            ref g,
+            ref p,
+            ref q,
+        } = self.parameters;
+
+        let w = match number::invmod(&signature.s, q) {
+            Ok(w) => w,
+            Err(_) => return false,
+        };
+        let u1 = (message_hash(message) * &w) % q;
+        let u2 = (&signature.r * &w) % q;
+        let v = ((g.modpow(&u1, p) * self.y.modpow(&u2, p)) % p) % q;
+
+        v == signature.r
+    }
+}
+
+impl Dsa {
+    pub fn new(parameters: DsaParameters) -> Self {
+        let mut rng = rand::thread_rng();
+        let x = rng.gen_biguint_range(&BigUint::one(), &parameters.q);
+        let y = parameters.g.modpow(&x, &parameters.p);
+
+        Dsa { parameters, x, y }
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            parameters: self.parameters.clone(),
+            y: self.y.clone(),
+        }
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        let mut rng = rand::thread_rng();
+
+        loop {
+            let k = rng.gen_biguint_range(&BigUint::one(), &self.parameters.q);
+
+            if let Ok(signature) = self.sign_with_nonce(message, &k) {
+                return signature;
+            }
+        }
+    }
+
+    pub fn sign_with_nonce(&self, message: &[u8], k: &BigUint) -> Result<Signature> {
+        let DsaParameters {
+            ref g,
+            ref p,
+            ref q,
+        } = self.parameters;
+
+        let r = g.modpow(k, p) % q;
+        if r.is_zero() {
+            bail!("nonce produced a zero r")
+        }
+
+        let k_inverse = number::invmod(k, q)?;
+        let s = (k_inverse * (message_hash(message) + &self.x * &r)) % q;
+        if s.is_zero() {
+            bail!("nonce produced a zero s")
+        }
+
+        Ok(Signature { r, s })
+    }
+}
+
+pub fn message_hash(message: &[u8]) -> BigUint {
+    BigUint::from_bytes_be(&sha::sha1(message))
+}
+
+pub fn private_key_from_nonce(
+    parameters: &DsaParameters,
+    hash: &BigUint,
+    signature: &Signature,
+    k: &BigUint,
+) -> Result<BigUint> {
+    let q = &parameters.q;
+    let r_inverse = number::invmod(&signature.r, q)?;
+
+    // x = (s * k - H(m)) / r mod q
+    let s_k = (&signature.s * k) % q;
+    let numerator = (s_k + q - (hash % q)) % q;
+
+    Ok((numerator * r_inverse) % q)
+}
+
+pub fn private_key_fingerprint(x: &BigUint) -> String {
+    hex::encode(sha::sha1(x.to_str_radix(16).as_bytes()))
+}
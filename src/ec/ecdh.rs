@@ -0,0 +1,132 @@
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{Num, One, Zero};
+use rand;
+
+use super::montgomery::MontgomeryCurve;
+use super::parse_coefficient;
+use super::weierstrass::{Point, WeierstrassCurve};
+use dh;
+use utility::error::{Result, ResultExt};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EcdhParameters {
+    pub curve: WeierstrassCurve,
+    pub g: Point,
+    pub n: BigUint,
+}
+
+pub struct Ecdh {
+    pub parameters: EcdhParameters,
+    pub x: BigUint,
+    pub y: Point,
+}
+
+// Montgomery form parameters for an exchange that only ever sends u coordinates
+#[derive(Clone, Debug, PartialEq)]
+pub struct LadderEcdhParameters {
+    pub curve: MontgomeryCurve,
+    pub u: BigUint,
+    pub n: BigUint,
+}
+
+pub struct LadderEcdh {
+    pub parameters: LadderEcdhParameters,
+    pub x: BigUint,
+    pub public_key: BigUint,
+}
+
+impl EcdhParameters {
+    pub fn new(curve: WeierstrassCurve, g: Point, n: BigUint) -> Self {
+        EcdhParameters { curve, g, n }
+    }
+
+    pub fn from_decimal(a: &str, b: &str, p: &str, gx: &str, gy: &str, n: &str) -> Result<Self> {
+        let p = BigUint::from_str_radix(p, 10).chain_err(|| "could not parse p")?;
+        let a = parse_coefficient(a, &p).chain_err(|| "could not parse a")?;
+        let b = parse_coefficient(b, &p).chain_err(|| "could not parse b")?;
+        let g = Point::Affine(
+            BigUint::from_str_radix(gx, 10).chain_err(|| "could not parse gx")?,
+            BigUint::from_str_radix(gy, 10).chain_err(|| "could not parse gy")?,
+        );
+        let n = BigUint::from_str_radix(n, 10).chain_err(|| "could not parse n")?;
+
+        Ok(EcdhParameters {
+            curve: WeierstrassCurve::new(a, b, p)?,
+            g,
+            n,
+        })
+    }
+
+    pub fn is_valid_public_key(&self, y: &Point) -> bool {
+        self.curve.validate(y, &self.n).is_ok()
+    }
+}
+
+impl Ecdh {
+    pub fn new(parameters: EcdhParameters) -> Self {
+        let mut rng = rand::thread_rng();
+        let x = rng.gen_biguint_range(&BigUint::one(), &parameters.n);
+        let y = parameters.curve.scale(&parameters.g, &x);
+
+        Ecdh { parameters, x, y }
+    }
+
+    pub fn shared_secret(&self, public_key: &Point) -> Point {
+        self.parameters.curve.scale(public_key, &self.x)
+    }
+}
+
+impl LadderEcdhParameters {
+    pub fn new(curve: MontgomeryCurve, u: BigUint, n: BigUint) -> Self {
+        LadderEcdhParameters { curve, u, n }
+    }
+
+    pub fn from_decimal(a: &str, b: &str, p: &str, u: &str, n: &str) -> Result<Self> {
+        let p = BigUint::from_str_radix(p, 10).chain_err(|| "could not parse p")?;
+        let a = parse_coefficient(a, &p).chain_err(|| "could not parse a")?;
+        let b = parse_coefficient(b, &p).chain_err(|| "could not parse b")?;
+        let u = BigUint::from_str_radix(u, 10).chain_err(|| "could not parse u")?;
+        let n = BigUint::from_str_radix(n, 10).chain_err(|| "could not parse n")?;
+
+        Ok(LadderEcdhParameters {
+            curve: MontgomeryCurve::new(a, b, p)?,
+            u,
+            n,
+        })
+    }
+}
+
+impl LadderEcdh {
+    pub fn new(parameters: LadderEcdhParameters) -> Self {
+        let n = parameters.n.clone();
+
+        LadderEcdh::new_with_private_key_bound(parameters, &n)
+    }
+
+    // The private key is drawn from [1, bound) rather than [1, n)
+    pub fn new_with_private_key_bound(parameters: LadderEcdhParameters, bound: &BigUint) -> Self {
+        let mut rng = rand::thread_rng();
+        let x = rng.gen_biguint_range(&BigUint::one(), bound);
+        let public_key = parameters.curve.ladder(&parameters.u, &x);
+
+        LadderEcdh {
+            parameters,
+            x,
+            public_key,
+        }
+    }
+
+    pub fn shared_secret(&self, public_key: &BigUint) -> BigUint {
+        self.parameters.curve.ladder(public_key, &self.x)
+    }
+}
+
+// Both coordinates go into the key, so a point and its negation MAC differently
+pub fn mac(shared_secret: &Point, p: &BigUint, message: &[u8]) -> Result<Vec<u8>> {
+    let key = match *shared_secret {
+        Point::Infinity => BigUint::zero(),
+        Point::Affine(ref x, ref y) => x * p + y,
+    };
+
+    dh::mac(&key, message)
+}
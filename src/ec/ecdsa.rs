@@ -0,0 +1,134 @@
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use openssl::sha;
+use rand;
+
+use super::ecdh::EcdhParameters;
+use super::weierstrass::{Point, WeierstrassCurve};
+use dsa::Signature;
+use utility::error::Result;
+use utility::number;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EcdsaParameters {
+    pub curve: WeierstrassCurve,
+    pub g: Point,
+    pub n: BigUint,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublicKey {
+    pub parameters: EcdsaParameters,
+    pub q: Point,
+}
+
+pub struct Ecdsa {
+    pub parameters: EcdsaParameters,
+    pub d: BigUint,
+    pub q: Point,
+}
+
+impl EcdsaParameters {
+    pub fn new(curve: WeierstrassCurve, g: Point, n: BigUint) -> Self {
+        EcdsaParameters { curve, g, n }
+    }
+
+    // The same domain parameters as ECDH, so they are parsed the same way
+    pub fn from_decimal(a: &str, b: &str, p: &str, gx: &str, gy: &str, n: &str) -> Result<Self> {
+        let EcdhParameters { curve, g, n } = EcdhParameters::from_decimal(a, b, p, gx, gy, n)?;
+
+        Ok(EcdsaParameters { curve, g, n })
+    }
+}
+
+impl PublicKey {
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
+        let EcdsaParameters {
+            ref curve,
+            ref g,
+            ref n,
+        } = self.parameters;
+
+        if signature.r.is_zero() || &signature.r >= n {
+            return false;
+        }
+        if signature.s.is_zero() || &signature.s >= n {
+            return false;
+        }
+
+        let w = match number::invmod(&signature.s, n) {
+            Ok(w) => w,
+            Err(_) => return false,
+        };
+        let u1 = (message_hash(message, n) * &w) % n;
+        let u2 = (&signature.r * &w) % n;
+
+        match curve.add(&curve.scale(g, &u1), &curve.scale(&self.q, &u2)) {
+            Point::Infinity => false,
+            Point::Affine(x, _) => x % n == signature.r,
+        }
+    }
+}
+
+impl Ecdsa {
+    pub fn new(parameters: EcdsaParameters) -> Self {
+        let mut rng = rand::thread_rng();
+        let d = rng.gen_biguint_range(&BigUint::one(), &parameters.n);
+        let q = parameters.curve.scale(&parameters.g, &d);
+
+        Ecdsa { parameters, d, q }
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            parameters: self.parameters.clone(),
+            q: self.q.clone(),
+        }
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        let mut rng = rand::thread_rng();
+
+        loop {
+            let k = rng.gen_biguint_range(&BigUint::one(), &self.parameters.n);
+
+            if let Ok(signature) = self.sign_with_nonce(message, &k) {
+                return signature;
+            }
+        }
+    }
+
+    pub fn sign_with_nonce(&self, message: &[u8], k: &BigUint) -> Result<Signature> {
+        let EcdsaParameters {
+            ref curve,
+            ref g,
+            ref n,
+        } = self.parameters;
+
+        let r = match curve.scale(g, k) {
+            Point::Infinity => bail!("nonce produced the point at infinity"),
+            Point::Affine(x, _) => x % n,
+        };
+        if r.is_zero() {
+            bail!("nonce produced a zero r")
+        }
+
+        let k_inverse = number::invmod(k, n)?;
+        let s = (k_inverse * (message_hash(message, n) + &self.d * &r)) % n;
+        if s.is_zero() {
+            bail!("nonce produced a zero s")
+        }
+
+        Ok(Signature { r, s })
+    }
+}
+
+// SHA-256, truncated to the bit length of n
+pub fn message_hash(message: &[u8], n: &BigUint) -> BigUint {
+    let hash = BigUint::from_bytes_be(&sha::sha256(message));
+
+    match 256usize.checked_sub(n.bits()) {
+        Some(shift) => hash >> shift,
+        None => hash,
+    }
+}
@@ -0,0 +1,39 @@
+use num_bigint::BigUint;
+use num_traits::Num;
+
+use utility::error::{Result, ResultExt};
+
+pub mod ecdh;
+pub mod ecdsa;
+pub mod montgomery;
+pub mod weierstrass;
+
+fn add_mod(first: &BigUint, second: &BigUint, p: &BigUint) -> BigUint {
+    (first + second) % p
+}
+
+fn sub_mod(first: &BigUint, second: &BigUint, p: &BigUint) -> BigUint {
+    (first % p + p - second % p) % p
+}
+
+fn mul_mod(first: &BigUint, second: &BigUint, p: &BigUint) -> BigUint {
+    (first * second) % p
+}
+
+// p is always prime here, so Fermat's little theorem gives the inverse
+fn inv_mod(value: &BigUint, p: &BigUint) -> BigUint {
+    value.modpow(&(p - 2u32), p)
+}
+
+// Curve coefficients are commonly written as small negative numbers
+fn parse_coefficient(value: &str, p: &BigUint) -> Result<BigUint> {
+    match value.strip_prefix('-') {
+        Some(magnitude) => {
+            let magnitude =
+                BigUint::from_str_radix(magnitude, 10).chain_err(|| "invalid number")?;
+
+            Ok((p - magnitude % p) % p)
+        }
+        None => BigUint::from_str_radix(value, 10).chain_err(|| "invalid number"),
+    }
+}
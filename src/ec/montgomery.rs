@@ -0,0 +1,188 @@
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+use super::weierstrass::{Point, WeierstrassCurve};
+use super::{add_mod, inv_mod, mul_mod, sub_mod};
+use utility::error::Result;
+use utility::number;
+
+// Bv^2 = u^3 + Au^2 + u over the integers mod p
+#[derive(Clone, Debug, PartialEq)]
+pub struct MontgomeryCurve {
+    pub a: BigUint,
+    pub b: BigUint,
+    pub p: BigUint,
+}
+
+impl MontgomeryCurve {
+    pub fn new(a: BigUint, b: BigUint, p: BigUint) -> Result<Self> {
+        let a = a % &p;
+        let b = b % &p;
+
+        // B(A^2 - 4) must not vanish or the curve is singular
+        let a_squared_minus_four = sub_mod(&mul_mod(&a, &a, &p), &BigUint::from(4u32), &p);
+
+        if b.is_zero() || a_squared_minus_four.is_zero() {
+            bail!("curve is singular")
+        }
+
+        Ok(MontgomeryCurve { a, b, p })
+    }
+
+    pub fn v_squared(&self, u: &BigUint) -> BigUint {
+        let p = &self.p;
+        let u_squared = mul_mod(u, u, p);
+        let rhs = add_mod(
+            &add_mod(
+                &mul_mod(&u_squared, u, p),
+                &mul_mod(&self.a, &u_squared, p),
+                p,
+            ),
+            u,
+            p,
+        );
+
+        mul_mod(&rhs, &inv_mod(&self.b, p), p)
+    }
+
+    pub fn is_on_curve(&self, u: &BigUint, v: &BigUint) -> bool {
+        u < &self.p && v < &self.p && mul_mod(v, v, &self.p) == self.v_squared(u)
+    }
+
+    // An x-only check: u belongs to a point on this curve rather than on its twist
+    pub fn is_valid_u(&self, u: &BigUint) -> bool {
+        u < &self.p && number::is_quadratic_residue(&self.v_squared(u), &self.p)
+    }
+
+    pub fn v_from_u(&self, u: &BigUint) -> Option<BigUint> {
+        number::sqrt_mod(&self.v_squared(u), &self.p)
+    }
+
+    // The quadratic twist cB v^2 = u^3 + Au^2 + u for a non-square c, which holds every u
+    // that has no v on this curve
+    pub fn twist(&self) -> Result<Self> {
+        let mut c = BigUint::from(2u32);
+
+        while number::is_quadratic_residue(&c, &self.p) {
+            c += 1u32;
+        }
+
+        MontgomeryCurve::new(
+            self.a.clone(),
+            mul_mod(&c, &self.b, &self.p),
+            self.p.clone(),
+        )
+    }
+
+    // x-only scalar multiplication, with the point at infinity coming out as 0
+    pub fn ladder(&self, u: &BigUint, scalar: &BigUint) -> BigUint {
+        let p = &self.p;
+        let (mut u2, mut w2) = (BigUint::one(), BigUint::zero());
+        let (mut u3, mut w3) = (u % p, BigUint::one());
+
+        for bit in (0..p.bits()).rev() {
+            let swap = ((scalar >> bit) & BigUint::one()).is_one();
+
+            if swap {
+                ::std::mem::swap(&mut u2, &mut u3);
+                ::std::mem::swap(&mut w2, &mut w3);
+            }
+
+            let sum = sub_mod(&mul_mod(&u2, &u3, p), &mul_mod(&w2, &w3, p), p);
+            let difference = sub_mod(&mul_mod(&u2, &w3, p), &mul_mod(&w2, &u3, p), p);
+            u3 = mul_mod(&sum, &sum, p);
+            w3 = mul_mod(u, &mul_mod(&difference, &difference, p), p);
+
+            let u2_squared = mul_mod(&u2, &u2, p);
+            let w2_squared = mul_mod(&w2, &w2, p);
+            let u2w2 = mul_mod(&u2, &w2, p);
+            let square_difference = sub_mod(&u2_squared, &w2_squared, p);
+            let next_u2 = mul_mod(&square_difference, &square_difference, p);
+            let next_w2 = mul_mod(
+                &mul_mod(&BigUint::from(4u32), &u2w2, p),
+                &add_mod(
+                    &add_mod(&u2_squared, &mul_mod(&self.a, &u2w2, p), p),
+                    &w2_squared,
+                    p,
+                ),
+                p,
+            );
+            u2 = next_u2;
+            w2 = next_w2;
+
+            if swap {
+                ::std::mem::swap(&mut u2, &mut u3);
+                ::std::mem::swap(&mut w2, &mut w3);
+            }
+        }
+
+        mul_mod(&u2, &inv_mod(&w2, p), p)
+    }
+
+    // x = u / B + A / 3B and y = v / B, giving a = (3 - A^2) / 3B^2 and
+    // b = (2A^3 - 9A) / 27B^3
+    pub fn to_weierstrass(&self) -> Result<WeierstrassCurve> {
+        let p = &self.p;
+        let three = BigUint::from(3u32);
+        let a_squared = mul_mod(&self.a, &self.a, p);
+        let b_squared = mul_mod(&self.b, &self.b, p);
+        let b_cubed = mul_mod(&b_squared, &self.b, p);
+
+        let a = mul_mod(
+            &sub_mod(&three, &a_squared, p),
+            &inv_mod(&mul_mod(&three, &b_squared, p), p),
+            p,
+        );
+        let b = mul_mod(
+            &sub_mod(
+                &mul_mod(&BigUint::from(2u32), &mul_mod(&a_squared, &self.a, p), p),
+                &mul_mod(&BigUint::from(9u32), &self.a, p),
+                p,
+            ),
+            &inv_mod(&mul_mod(&BigUint::from(27u32), &b_cubed, p), p),
+            p,
+        );
+
+        WeierstrassCurve::new(a, b, p.clone())
+    }
+
+    pub fn u_to_weierstrass_x(&self, u: &BigUint) -> BigUint {
+        let p = &self.p;
+        let b_inverse = inv_mod(&self.b, p);
+        let shift = mul_mod(
+            &self.a,
+            &inv_mod(&mul_mod(&BigUint::from(3u32), &self.b, p), p),
+            p,
+        );
+
+        add_mod(&mul_mod(u, &b_inverse, p), &shift, p)
+    }
+
+    pub fn weierstrass_x_to_u(&self, x: &BigUint) -> BigUint {
+        let p = &self.p;
+        let shift = mul_mod(&self.a, &inv_mod(&BigUint::from(3u32), p), p);
+
+        sub_mod(&mul_mod(&self.b, x, p), &shift, p)
+    }
+
+    pub fn point_to_weierstrass(&self, u: &BigUint, v: &BigUint) -> Point {
+        Point::Affine(
+            self.u_to_weierstrass_x(u),
+            mul_mod(v, &inv_mod(&self.b, &self.p), &self.p),
+        )
+    }
+
+    // One of the two points with this u coordinate, in Weierstrass form
+    pub fn weierstrass_point(&self, u: &BigUint) -> Option<Point> {
+        self.v_from_u(u).map(|v| self.point_to_weierstrass(u, &v))
+    }
+
+    pub fn point_from_weierstrass(&self, point: &Point) -> Option<(BigUint, BigUint)> {
+        match *point {
+            Point::Infinity => None,
+            Point::Affine(ref x, ref y) => {
+                Some((self.weierstrass_x_to_u(x), mul_mod(&self.b, y, &self.p)))
+            }
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use rand;
+
+use super::{add_mod, inv_mod, mul_mod, sub_mod};
+use dlog::CyclicGroup;
+use utility::error::Result;
+use utility::number;
+
+// y^2 = x^3 + ax + b over the integers mod p
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeierstrassCurve {
+    pub a: BigUint,
+    pub b: BigUint,
+    pub p: BigUint,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Point {
+    Infinity,
+    Affine(BigUint, BigUint),
+}
+
+// Homogeneous coordinates with x = X / Z and y = Y / Z, and Z = 0 at infinity
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectivePoint {
+    pub x: BigUint,
+    pub y: BigUint,
+    pub z: BigUint,
+}
+
+impl WeierstrassCurve {
+    pub fn new(a: BigUint, b: BigUint, p: BigUint) -> Result<Self> {
+        let a = a % &p;
+        let b = b % &p;
+
+        // 4a^3 + 27b^2 must not vanish or the curve is singular
+        let discriminant = add_mod(
+            &(BigUint::from(4u32) * a.modpow(&BigUint::from(3u32), &p)),
+            &(BigUint::from(27u32) * mul_mod(&b, &b, &p)),
+            &p,
+        );
+
+        if discriminant.is_zero() {
+            bail!("curve is singular")
+        }
+
+        Ok(WeierstrassCurve { a, b, p })
+    }
+
+    pub fn y_squared(&self, x: &BigUint) -> BigUint {
+        let p = &self.p;
+        let x_cubed = x.modpow(&BigUint::from(3u32), p);
+
+        add_mod(&add_mod(&x_cubed, &mul_mod(&self.a, x, p), p), &self.b, p)
+    }
+
+    pub fn is_on_curve(&self, point: &Point) -> bool {
+        match *point {
+            Point::Infinity => true,
+            Point::Affine(ref x, ref y) => {
+                x < &self.p && y < &self.p && mul_mod(y, y, &self.p) == self.y_squared(x)
+            }
+        }
+    }
+
+    pub fn validate(&self, point: &Point, order: &BigUint) -> Result<()> {
+        if *point == Point::Infinity {
+            bail!("point is the point at infinity")
+        }
+
+        if !self.is_on_curve(point) {
+            bail!("point is not on the curve")
+        }
+
+        if self.scale(point, order) != Point::Infinity {
+            bail!("point is not in the prime order subgroup")
+        }
+
+        Ok(())
+    }
+
+    pub fn random_point(&self) -> Point {
+        let mut rng = rand::thread_rng();
+
+        loop {
+            let x = rng.gen_biguint_below(&self.p);
+
+            if let Some(y) = number::sqrt_mod(&self.y_squared(&x), &self.p) {
+                return Point::Affine(x, y);
+            }
+        }
+    }
+
+    // The same curve with a different constant term, which the addition formulas never
+    // look at
+    pub fn with_b(&self, b: BigUint) -> Result<Self> {
+        WeierstrassCurve::new(self.a.clone(), b, self.p.clone())
+    }
+
+    // A random point of order r, for r a prime factor of the curve order
+    pub fn point_of_order(&self, curve_order: &BigUint, r: &BigUint) -> Result<Point> {
+        if !(curve_order % r).is_zero() {
+            bail!("order does not divide the curve order")
+        }
+
+        // Strip every factor of r from the cofactor, since the r-part of the group need not
+        // be cyclic, then step down to a point of exact order r
+        let mut cofactor = curve_order / r;
+
+        while (&cofactor % r).is_zero() {
+            cofactor /= r;
+        }
+
+        loop {
+            let mut point = self.scale(&self.random_point(), &cofactor);
+
+            if point == Point::Infinity {
+                continue;
+            }
+
+            loop {
+                let next = self.scale(&point, r);
+
+                if next == Point::Infinity {
+                    return Ok(point);
+                }
+
+                point = next;
+            }
+        }
+    }
+
+    pub fn negate(&self, point: &Point) -> Point {
+        match *point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(ref x, ref y) => {
+                Point::Affine(x.clone(), sub_mod(&BigUint::zero(), y, &self.p))
+            }
+        }
+    }
+
+    pub fn add(&self, first: &Point, second: &Point) -> Point {
+        let p = &self.p;
+
+        match (first, second) {
+            (&Point::Infinity, point) | (point, &Point::Infinity) => point.clone(),
+            (Point::Affine(x1, y1), Point::Affine(x2, y2)) => {
+                if x1 == x2 && add_mod(y1, y2, p).is_zero() {
+                    return Point::Infinity;
+                }
+
+                let slope = if x1 == x2 {
+                    let numerator = add_mod(
+                        &mul_mod(&BigUint::from(3u32), &mul_mod(x1, x1, p), p),
+                        &self.a,
+                        p,
+                    );
+                    mul_mod(&numerator, &inv_mod(&add_mod(y1, y1, p), p), p)
+                } else {
+                    mul_mod(&sub_mod(y2, y1, p), &inv_mod(&sub_mod(x2, x1, p), p), p)
+                };
+
+                let x3 = sub_mod(&sub_mod(&mul_mod(&slope, &slope, p), x1, p), x2, p);
+                let y3 = sub_mod(&mul_mod(&slope, &sub_mod(x1, &x3, p), p), y1, p);
+
+                Point::Affine(x3, y3)
+            }
+        }
+    }
+
+    pub fn double(&self, point: &Point) -> Point {
+        self.add(point, point)
+    }
+
+    pub fn scale(&self, point: &Point, scalar: &BigUint) -> Point {
+        self.to_affine(&self.scale_projective(&self.to_projective(point), scalar))
+    }
+
+    pub fn to_projective(&self, point: &Point) -> ProjectivePoint {
+        match *point {
+            Point::Infinity => ProjectivePoint {
+                x: BigUint::zero(),
+                y: BigUint::one(),
+                z: BigUint::zero(),
+            },
+            Point::Affine(ref x, ref y) => ProjectivePoint {
+                x: x.clone(),
+                y: y.clone(),
+                z: BigUint::one(),
+            },
+        }
+    }
+
+    pub fn to_affine(&self, point: &ProjectivePoint) -> Point {
+        if point.z.is_zero() {
+            return Point::Infinity;
+        }
+
+        let z_inverse = inv_mod(&point.z, &self.p);
+
+        Point::Affine(
+            mul_mod(&point.x, &z_inverse, &self.p),
+            mul_mod(&point.y, &z_inverse, &self.p),
+        )
+    }
+
+    // add-1998-cmo-2 from the Explicit-Formulas Database
+    pub fn add_projective(
+        &self,
+        first: &ProjectivePoint,
+        second: &ProjectivePoint,
+    ) -> ProjectivePoint {
+        let p = &self.p;
+
+        if first.z.is_zero() {
+            return second.clone();
+        }
+        if second.z.is_zero() {
+            return first.clone();
+        }
+
+        let y1z2 = mul_mod(&first.y, &second.z, p);
+        let x1z2 = mul_mod(&first.x, &second.z, p);
+        let z1z2 = mul_mod(&first.z, &second.z, p);
+        let u = sub_mod(&mul_mod(&second.y, &first.z, p), &y1z2, p);
+        let v = sub_mod(&mul_mod(&second.x, &first.z, p), &x1z2, p);
+
+        if v.is_zero() {
+            return match u.is_zero() {
+                true => self.double_projective(first),
+                false => self.to_projective(&Point::Infinity),
+            };
+        }
+
+        let uu = mul_mod(&u, &u, p);
+        let vv = mul_mod(&v, &v, p);
+        let vvv = mul_mod(&v, &vv, p);
+        let r = mul_mod(&vv, &x1z2, p);
+        let a = sub_mod(
+            &sub_mod(&mul_mod(&uu, &z1z2, p), &vvv, p),
+            &add_mod(&r, &r, p),
+            p,
+        );
+
+        ProjectivePoint {
+            x: mul_mod(&v, &a, p),
+            y: sub_mod(
+                &mul_mod(&u, &sub_mod(&r, &a, p), p),
+                &mul_mod(&vvv, &y1z2, p),
+                p,
+            ),
+            z: mul_mod(&vvv, &z1z2, p),
+        }
+    }
+
+    // dbl-2007-bl from the Explicit-Formulas Database
+    pub fn double_projective(&self, point: &ProjectivePoint) -> ProjectivePoint {
+        let p = &self.p;
+
+        if point.z.is_zero() || point.y.is_zero() {
+            return self.to_projective(&Point::Infinity);
+        }
+
+        let xx = mul_mod(&point.x, &point.x, p);
+        let zz = mul_mod(&point.z, &point.z, p);
+        let w = add_mod(
+            &mul_mod(&self.a, &zz, p),
+            &mul_mod(&BigUint::from(3u32), &xx, p),
+            p,
+        );
+        let s = mul_mod(&BigUint::from(2u32), &mul_mod(&point.y, &point.z, p), p);
+        let ss = mul_mod(&s, &s, p);
+        let sss = mul_mod(&s, &ss, p);
+        let r = mul_mod(&point.y, &s, p);
+        let rr = mul_mod(&r, &r, p);
+        let x_plus_r = add_mod(&point.x, &r, p);
+        let b = sub_mod(&sub_mod(&mul_mod(&x_plus_r, &x_plus_r, p), &xx, p), &rr, p);
+        let h = sub_mod(&mul_mod(&w, &w, p), &add_mod(&b, &b, p), p);
+
+        ProjectivePoint {
+            x: mul_mod(&h, &s, p),
+            y: sub_mod(
+                &mul_mod(&w, &sub_mod(&b, &h, p), p),
+                &add_mod(&rr, &rr, p),
+                p,
+            ),
+            z: sss,
+        }
+    }
+
+    pub fn scale_projective(&self, point: &ProjectivePoint, scalar: &BigUint) -> ProjectivePoint {
+        let mut result = self.to_projective(&Point::Infinity);
+
+        for bit in (0..scalar.bits()).rev() {
+            result = self.double_projective(&result);
+
+            if ((scalar >> bit) & BigUint::one()).is_one() {
+                result = self.add_projective(&result, point);
+            }
+        }
+
+        result
+    }
+}
+
+impl CyclicGroup for WeierstrassCurve {
+    type Element = Point;
+
+    fn identity(&self) -> Point {
+        Point::Infinity
+    }
+
+    fn combine(&self, first: &Point, second: &Point) -> Point {
+        self.add(first, second)
+    }
+
+    fn scale(&self, element: &Point, scalar: &BigUint) -> Point {
+        WeierstrassCurve::scale(self, element, scalar)
+    }
+
+    fn fingerprint(&self, element: &Point) -> u64 {
+        match *element {
+            Point::Infinity => 0,
+            Point::Affine(ref x, _) => x
+                .to_bytes_le()
+                .iter()
+                .take(8)
+                .rev()
+                .fold(0, |fingerprint, &byte| fingerprint << 8 | u64::from(byte)),
+        }
+    }
+}
@@ -0,0 +1,1132 @@
+use hex;
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::{self, distributions::Standard, Rng};
+
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use aes::{self, GcmMessage};
+use dlog::{self, MultiplicativeGroup};
+use dsa;
+use ec::ecdsa::{self, Ecdsa, EcdsaParameters};
+use gf128::{self, Element};
+use hash::md::{Compression, MdHash};
+use hash::md4;
+use rsa::{self, HashAlgorithm, Rsa};
+use utility::error::{Result, ResultExt};
+use utility::file;
+use utility::number;
+use utility::xor;
+
+pub struct CascadedCollision {
+    pub compression_calls: usize,
+    pub messages: (Vec<u8>, Vec<u8>),
+}
+
+pub struct SecondPreimage {
+    pub compression_calls: usize,
+    pub message: Vec<u8>,
+}
+
+pub struct CompressionCollision {
+    pub blocks: (Vec<u8>, Vec<u8>),
+    pub compression_calls: usize,
+    pub state: Vec<u8>,
+}
+
+// Level 0 holds the 2^k leaf states, each with the block that carries it into its parent
+// on the next level, and the last level is a single pair collapsing into the root
+pub struct DiamondStructure {
+    pub compression_calls: usize,
+    pub levels: Vec<Vec<(Vec<u8>, Vec<u8>)>>,
+    pub root: Vec<u8>,
+}
+
+pub struct ExpandableMessage {
+    pub block_pairs: Vec<(Vec<u8>, Vec<u8>)>,
+    pub compression_calls: usize,
+    pub state: Vec<u8>,
+}
+
+pub struct Md4Collision {
+    pub attempts: usize,
+    pub messages: (Vec<u8>, Vec<u8>),
+}
+
+// Bits are numbered from 1 as in Wang et al., and Equal compares against the step output
+// the given number of places back
+enum Md4Condition {
+    Zero(u32),
+    One(u32),
+    Equal(u32, usize),
+}
+
+pub struct Multicollision {
+    pub block_pairs: Vec<(Vec<u8>, Vec<u8>)>,
+    pub compression_calls: usize,
+    pub state: Vec<u8>,
+}
+
+static WANG_ROUND_1_CONDITIONS: [&[Md4Condition]; 16] = [
+    // a1
+    &[Md4Condition::Equal(7, 1)],
+    // d1
+    &[
+        Md4Condition::Zero(7),
+        Md4Condition::Equal(8, 1),
+        Md4Condition::Equal(11, 1),
+    ],
+    // c1
+    &[
+        Md4Condition::One(7),
+        Md4Condition::One(8),
+        Md4Condition::Zero(11),
+        Md4Condition::Equal(26, 1),
+    ],
+    // b1
+    &[
+        Md4Condition::One(7),
+        Md4Condition::Zero(8),
+        Md4Condition::Zero(11),
+        Md4Condition::Zero(26),
+    ],
+    // a2
+    &[
+        Md4Condition::One(8),
+        Md4Condition::One(11),
+        Md4Condition::Zero(26),
+        Md4Condition::Equal(14, 1),
+    ],
+    // d2
+    &[
+        Md4Condition::Zero(14),
+        Md4Condition::Equal(19, 1),
+        Md4Condition::Equal(20, 1),
+        Md4Condition::Equal(21, 1),
+        Md4Condition::Equal(22, 1),
+        Md4Condition::One(26),
+    ],
+    // c2
+    &[
+        Md4Condition::Equal(13, 1),
+        Md4Condition::Zero(14),
+        Md4Condition::Equal(15, 1),
+        Md4Condition::Zero(19),
+        Md4Condition::Zero(20),
+        Md4Condition::One(21),
+        Md4Condition::Zero(22),
+    ],
+    // b2
+    &[
+        Md4Condition::One(13),
+        Md4Condition::One(14),
+        Md4Condition::Zero(15),
+        Md4Condition::Equal(17, 1),
+        Md4Condition::Zero(19),
+        Md4Condition::Zero(20),
+        Md4Condition::Zero(21),
+        Md4Condition::Zero(22),
+    ],
+    // a3
+    &[
+        Md4Condition::One(13),
+        Md4Condition::One(14),
+        Md4Condition::One(15),
+        Md4Condition::Zero(17),
+        Md4Condition::Zero(19),
+        Md4Condition::Zero(20),
+        Md4Condition::Zero(21),
+        Md4Condition::One(22),
+        Md4Condition::Equal(23, 1),
+        Md4Condition::Equal(26, 1),
+    ],
+    // d3
+    &[
+        Md4Condition::One(13),
+        Md4Condition::One(14),
+        Md4Condition::One(15),
+        Md4Condition::Zero(17),
+        Md4Condition::Zero(20),
+        Md4Condition::One(21),
+        Md4Condition::One(22),
+        Md4Condition::Zero(23),
+        Md4Condition::One(26),
+        Md4Condition::Equal(30, 1),
+    ],
+    // c3
+    &[
+        Md4Condition::One(17),
+        Md4Condition::Zero(20),
+        Md4Condition::Zero(21),
+        Md4Condition::Zero(22),
+        Md4Condition::Zero(23),
+        Md4Condition::Zero(26),
+        Md4Condition::One(30),
+        Md4Condition::Equal(32, 1),
+    ],
+    // b3
+    &[
+        Md4Condition::Zero(20),
+        Md4Condition::One(21),
+        Md4Condition::One(22),
+        Md4Condition::Equal(23, 1),
+        Md4Condition::One(26),
+        Md4Condition::Zero(30),
+        Md4Condition::Zero(32),
+    ],
+    // a4
+    &[
+        Md4Condition::Zero(23),
+        Md4Condition::Zero(26),
+        Md4Condition::Equal(27, 1),
+        Md4Condition::Equal(29, 1),
+        Md4Condition::One(30),
+        Md4Condition::Zero(32),
+    ],
+    // d4
+    &[
+        Md4Condition::Zero(23),
+        Md4Condition::Zero(26),
+        Md4Condition::One(27),
+        Md4Condition::One(29),
+        Md4Condition::Zero(30),
+        Md4Condition::One(32),
+    ],
+    // c4
+    &[
+        Md4Condition::Equal(19, 1),
+        Md4Condition::One(23),
+        Md4Condition::One(26),
+        Md4Condition::Zero(27),
+        Md4Condition::Zero(29),
+        Md4Condition::Zero(30),
+    ],
+    // b4
+    &[
+        Md4Condition::Zero(19),
+        Md4Condition::One(26),
+        Md4Condition::One(27),
+        Md4Condition::One(29),
+        Md4Condition::Zero(30),
+    ],
+];
+static WANG_A5_CONDITIONS: [Md4Condition; 5] = [
+    Md4Condition::Equal(19, 2),
+    Md4Condition::One(26),
+    Md4Condition::Zero(27),
+    Md4Condition::One(29),
+    Md4Condition::One(32),
+];
+static WANG_D5_CONDITIONS: [Md4Condition; 5] = [
+    Md4Condition::Equal(19, 1),
+    Md4Condition::Equal(26, 2),
+    Md4Condition::Equal(27, 2),
+    Md4Condition::Equal(29, 2),
+    Md4Condition::Equal(32, 2),
+];
+
+// p - 1 and q - 1 for duplicate-signature RSA keys are built from primes this size
+static SMOOTH_FACTOR_BITS: usize = 16;
+
+pub fn forge_e3_rsa_signature(
+    message: &[u8],
+    public_key: &rsa::PublicKey,
+    algorithm: HashAlgorithm,
+) -> Result<Vec<u8>> {
+    if public_key.e != BigUint::from(3u32) {
+        bail!("public exponent must be 3")
+    }
+
+    let byte_len = public_key.byte_len();
+    let digest_info = algorithm.digest_info(message);
+
+    // 00 01 FF 00 DigestInfo, followed by garbage the sloppy verifier never looks at
+    let mut block = vec![0x00, 0x01, 0xff, 0x00];
+    block.extend_from_slice(&digest_info);
+    let prefix_len = block.len();
+
+    if byte_len <= prefix_len {
+        bail!("modulus too short for signature block")
+    }
+
+    block.resize(byte_len, 0x00);
+
+    // Round the cube root up so its cube only disturbs the garbage bytes
+    let target = BigUint::from_bytes_be(&block);
+    let mut signature = target.cbrt();
+    if &signature * &signature * &signature < target {
+        signature += 1u32;
+    }
+
+    let forged_block = number::to_padded_bytes(&(&signature * &signature * &signature), byte_len);
+
+    if forged_block.len() != byte_len || forged_block[..prefix_len] != block[..prefix_len] {
+        bail!("not enough garbage space to forge signature")
+    }
+
+    Ok(number::to_padded_bytes(&signature, byte_len))
+}
+
+pub fn forge_dsa_zero_generator_signature(public_key: &dsa::PublicKey) -> Result<dsa::Signature> {
+    if !public_key.parameters.g.is_zero() {
+        bail!("generator must be 0")
+    }
+
+    // With g = 0, v = (0^u1 * y^u2 mod p) mod q is always 0, so r = 0 matches any message
+    let mut rng = rand::thread_rng();
+    let s = rng.gen_biguint_range(&BigUint::one(), &public_key.parameters.q);
+
+    Ok(dsa::Signature {
+        r: BigUint::zero(),
+        s,
+    })
+}
+
+pub fn forge_dsa_magic_signature(public_key: &dsa::PublicKey) -> Result<dsa::Signature> {
+    let dsa::DsaParameters {
+        ref g,
+        ref p,
+        ref q,
+    } = public_key.parameters;
+
+    if *g != p + 1u32 {
+        bail!("generator must be p + 1")
+    }
+
+    // With g = p + 1, g^u1 is always 1, so r = (y^z mod p) mod q and s = r / z verify
+    // against any message
+    let mut rng = rand::thread_rng();
+    let z = rng.gen_biguint_range(&BigUint::one(), q);
+    let r = public_key.y.modpow(&z, p) % q;
+    let s = (&r * number::invmod(&z, q)?) % q;
+
+    Ok(dsa::Signature { r, s })
+}
+
+pub fn forge_cbc_mac_iv(message: &[u8], iv: &[u8], forged_first_block: &[u8]) -> Result<Vec<u8>> {
+    if message.len() < iv.len() || forged_first_block.len() != iv.len() {
+        bail!("forged block must replace a whole block of the message")
+    }
+
+    // The first block only enters the MAC as P1 ^ IV, so moving the change into the IV
+    // leaves the MAC untouched
+    let difference = xor::byte_slice_xor(&message[..iv.len()], forged_first_block)?;
+
+    xor::byte_slice_xor(iv, &difference)
+}
+
+pub fn forge_cbc_mac_extension(
+    message: &[u8],
+    mac: &[u8],
+    extension: &[u8],
+    block_size: usize,
+) -> Result<Vec<u8>> {
+    if mac.len() != block_size || extension.len() < block_size {
+        bail!("extension must be at least one block long")
+    }
+
+    // Chaining state after the padded message is its MAC, so XORing it into the extension's
+    // first block restarts the chain as if from a zero IV
+    let mut forged_message = message.to_vec();
+    aes::pkcs7_pad_vec(&mut forged_message, block_size)?;
+    forged_message.append(&mut xor::byte_slice_xor(&extension[..block_size], mac)?);
+    forged_message.extend_from_slice(&extension[block_size..]);
+
+    Ok(forged_message)
+}
+
+pub fn forge_cbc_mac_hash_collision(
+    prefix: &[u8],
+    target_mac: &[u8],
+    key: &[u8],
+    iv: &[u8],
+) -> Result<Vec<u8>> {
+    let block_size = iv.len();
+
+    if prefix.is_empty() || aes::padded_len(prefix.len(), block_size) != prefix.len() {
+        bail!("prefix must be a whole number of blocks")
+    }
+    if target_mac.len() != block_size {
+        bail!("target MAC must be one block long")
+    }
+
+    // The chaining state after the prefix is the last block of its CBC encryption
+    let ciphertext = aes::encrypt_cbc_text(prefix, key, iv)?;
+    let chaining_state = &ciphertext[ciphertext.len() - block_size..];
+
+    // A final block of D(target) ^ state encrypts straight to the target. The forged
+    // message is block aligned, so no padding gets added after it.
+    let decrypted_target = aes::decrypt_ecb_text(target_mac, key)?;
+    let bridge_block = xor::byte_slice_xor(&decrypted_target, chaining_state)?;
+
+    let mut forged_message = prefix.to_vec();
+    forged_message.extend_from_slice(&bridge_block);
+
+    Ok(forged_message)
+}
+
+impl DiamondStructure {
+    pub fn k(&self) -> usize {
+        self.levels.len()
+    }
+
+    pub fn prediction<C: Compression>(
+        &self,
+        hash: &MdHash<C>,
+        prefix_len: usize,
+    ) -> Result<Vec<u8>> {
+        if aes::padded_len(prefix_len, hash.block_size()) != prefix_len {
+            bail!("prefix must be a whole number of blocks")
+        }
+
+        // The herded message is the prefix, one linking block, then a block per level
+        let message_len = prefix_len + (self.k() + 1) * hash.block_size();
+        hash.compress_blocks(&self.root, &hash.padding(message_len))
+    }
+
+    // Every pair of blocks on a level must lead to the state above them, and the last pair
+    // to the root, or a message herded through the structure misses the prediction
+    pub fn is_valid<C: Compression>(&self, hash: &MdHash<C>) -> bool {
+        if self.levels.is_empty() {
+            return false;
+        }
+
+        self.levels.iter().enumerate().all(|(level, nodes)| {
+            nodes.iter().enumerate().all(|(index, (state, block))| {
+                let expected = match self.levels.get(level + 1) {
+                    Some(next_nodes) => next_nodes.get(index / 2).map(|(state, _)| state),
+                    None => Some(&self.root),
+                };
+
+                match (hash.compress(state, block), expected) {
+                    (Ok(next_state), Some(expected)) => &next_state == expected,
+                    _ => false,
+                }
+            })
+        })
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        let mut contents = String::new();
+
+        for (level, nodes) in self.levels.iter().enumerate() {
+            for (state, block) in nodes {
+                contents += &format!("{} {} {}\n", level, hex::encode(state), hex::encode(block));
+            }
+        }
+
+        contents += &format!("root {}\n", hex::encode(&self.root));
+        file::write_bytes(path, contents.as_bytes())
+    }
+
+    pub fn load(path: &str) -> Result<Self> {
+        let mut levels: Vec<Vec<(Vec<u8>, Vec<u8>)>> = Vec::new();
+        let mut root = None;
+
+        for line in file::buffered_file_reader(path)?.lines() {
+            let line = line.chain_err(|| "line was not found")?;
+            let fields: Vec<&str> = line.split(' ').collect();
+
+            match fields[..] {
+                ["root", state] => {
+                    root = Some(hex::decode(state).chain_err(|| "could not decode hex string")?)
+                }
+                [level, state, block] => {
+                    let level: usize = level.parse().chain_err(|| "could not parse level")?;
+                    let state = hex::decode(state).chain_err(|| "could not decode hex string")?;
+                    let block = hex::decode(block).chain_err(|| "could not decode hex string")?;
+
+                    if level > levels.len() {
+                        bail!("diamond structure levels are out of order")
+                    }
+
+                    if level == levels.len() {
+                        levels.push(Vec::new());
+                    }
+
+                    levels[level].push((state, block));
+                }
+                _ => bail!("malformed diamond structure line"),
+            }
+        }
+
+        let k = levels.len();
+
+        if k == 0 {
+            bail!("diamond structure has no levels")
+        }
+
+        for (level, nodes) in levels.iter().enumerate() {
+            if nodes.len() != 1 << (k - level) {
+                bail!("diamond structure level has the wrong number of states")
+            }
+        }
+
+        let root = match root {
+            Some(root) => root,
+            None => bail!("diamond structure has no root"),
+        };
+
+        // Every state must be the size of the root, and every block the same size
+        let block_len = levels[0][0].1.len();
+
+        for (state, block) in levels.iter().flatten() {
+            if state.len() != root.len() || block.len() != block_len {
+                bail!("diamond structure states or blocks differ in size")
+            }
+        }
+
+        Ok(DiamondStructure {
+            compression_calls: 0,
+            levels,
+            root,
+        })
+    }
+}
+
+impl ExpandableMessage {
+    pub fn min_blocks(&self) -> usize {
+        self.block_pairs.len()
+    }
+
+    pub fn max_blocks(&self) -> usize {
+        self.block_pairs.len() + (1 << self.block_pairs.len()) - 1
+    }
+
+    // Pair i offers either a single block or 2^(k-1-i) + 1 blocks, so the extra length
+    // past k blocks picks the long side of each pair bit by bit
+    pub fn message(&self, block_count: usize) -> Result<Vec<u8>> {
+        if block_count < self.min_blocks() || block_count > self.max_blocks() {
+            bail!("expandable message cannot reach the requested length")
+        }
+
+        let extra_blocks = block_count - self.min_blocks();
+        let stages = self.block_pairs.len();
+        let mut message = Vec::new();
+
+        for (stage, (short, long)) in self.block_pairs.iter().enumerate() {
+            match (extra_blocks >> (stages - 1 - stage)) & 1 {
+                0 => message.extend_from_slice(short),
+                _ => message.extend_from_slice(long),
+            }
+        }
+
+        Ok(message)
+    }
+}
+
+impl Multicollision {
+    pub fn message_count(&self) -> usize {
+        1 << self.block_pairs.len()
+    }
+
+    // Bit i of the index picks which block of pair i goes into the message
+    pub fn message(&self, index: usize) -> Vec<u8> {
+        let mut message = Vec::new();
+
+        for (bit, (first, second)) in self.block_pairs.iter().enumerate() {
+            match (index >> bit) & 1 {
+                0 => message.extend_from_slice(first),
+                _ => message.extend_from_slice(second),
+            }
+        }
+
+        message
+    }
+
+    pub fn messages(&self) -> Vec<Vec<u8>> {
+        (0..self.message_count())
+            .map(|index| self.message(index))
+            .collect()
+    }
+}
+
+pub fn forge_compression_collision<C: Compression>(
+    hash: &MdHash<C>,
+    state: &[u8],
+) -> Result<CompressionCollision> {
+    forge_compression_collision_between(hash, state, state)
+}
+
+// The first block is compressed from the first state and the second block from the
+// second, so the two states can differ
+pub fn forge_compression_collision_between<C: Compression>(
+    hash: &MdHash<C>,
+    first_state: &[u8],
+    second_state: &[u8],
+) -> Result<CompressionCollision> {
+    let mut rng = rand::thread_rng();
+    let mut first_seen_states: HashMap<Vec<u8>, Vec<u8>> = HashMap::new();
+    let mut second_seen_states: HashMap<Vec<u8>, Vec<u8>> = HashMap::new();
+    let mut compression_calls = 0;
+
+    loop {
+        let first_block: Vec<u8> = rng.sample_iter(&Standard).take(hash.block_size()).collect();
+        let next_state = hash.compress(first_state, &first_block)?;
+        compression_calls += 1;
+
+        match second_seen_states.get(&next_state) {
+            Some(second_block) if *second_block != first_block => {
+                return Ok(CompressionCollision {
+                    blocks: (first_block, second_block.clone()),
+                    compression_calls,
+                    state: next_state,
+                })
+            }
+            _ => {}
+        }
+        first_seen_states.insert(next_state, first_block);
+
+        let second_block: Vec<u8> = rng.sample_iter(&Standard).take(hash.block_size()).collect();
+        let next_state = hash.compress(second_state, &second_block)?;
+        compression_calls += 1;
+
+        match first_seen_states.get(&next_state) {
+            Some(first_block) if *first_block != second_block => {
+                return Ok(CompressionCollision {
+                    blocks: (first_block.clone(), second_block),
+                    compression_calls,
+                    state: next_state,
+                })
+            }
+            _ => {}
+        }
+        second_seen_states.insert(next_state, second_block);
+    }
+}
+
+pub fn forge_multicollision<C: Compression>(
+    hash: &MdHash<C>,
+    state: &[u8],
+    block_count: usize,
+) -> Result<Multicollision> {
+    let mut multicollision = Multicollision {
+        block_pairs: Vec::with_capacity(block_count),
+        compression_calls: 0,
+        state: state.to_vec(),
+    };
+
+    for _ in 0..block_count {
+        extend_multicollision(hash, &mut multicollision)?;
+    }
+
+    Ok(multicollision)
+}
+
+pub fn extend_multicollision<C: Compression>(
+    hash: &MdHash<C>,
+    multicollision: &mut Multicollision,
+) -> Result<()> {
+    let collision = forge_compression_collision(hash, &multicollision.state)?;
+
+    multicollision.block_pairs.push(collision.blocks);
+    multicollision.compression_calls += collision.compression_calls;
+    multicollision.state = collision.state;
+
+    Ok(())
+}
+
+pub fn forge_cascaded_collision<C, D>(f: &MdHash<C>, g: &MdHash<D>) -> Result<CascadedCollision>
+where
+    C: Compression,
+    D: Compression,
+{
+    if f.block_size() != g.block_size() {
+        bail!("cascaded hashes must share a block size")
+    }
+
+    // Enough f collisions that a g collision is likely among them by the birthday bound,
+    // adding one more stage (doubling the messages) each time we come up short
+    let mut multicollision = forge_multicollision(f, &f.initial_state, g.state_size() * 4)?;
+    let mut g_calls = 0;
+
+    loop {
+        let block_count = multicollision.block_pairs.len();
+        let padding = g.padding(block_count * g.block_size());
+        let mut seen_hashes = HashMap::new();
+        let mut states = vec![g.initial_state.clone()];
+
+        // Count through the messages with the first block as the most significant choice,
+        // so consecutive messages share the longest prefix and each prefix state is only
+        // compressed once
+        for counter in 0..multicollision.message_count() {
+            let first_changed = match counter {
+                0 => 0,
+                _ => block_count - 1 - counter.trailing_zeros() as usize,
+            };
+            states.truncate(first_changed + 1);
+
+            for depth in first_changed..block_count {
+                let (ref first, ref second) = multicollision.block_pairs[depth];
+                let block = match (counter >> (block_count - 1 - depth)) & 1 {
+                    0 => first,
+                    _ => second,
+                };
+
+                let next_state = g.compress(&states[depth], block)?;
+                g_calls += 1;
+                states.push(next_state);
+            }
+
+            let digest = g.compress_blocks(&states[block_count], &padding)?;
+            g_calls += padding.len() / g.block_size();
+
+            let index = (0..block_count).fold(0, |index, depth| {
+                index | ((counter >> (block_count - 1 - depth)) & 1) << depth
+            });
+
+            if let Some(other_index) = seen_hashes.insert(digest, index) {
+                return Ok(CascadedCollision {
+                    compression_calls: multicollision.compression_calls + g_calls,
+                    messages: (
+                        multicollision.message(other_index),
+                        multicollision.message(index),
+                    ),
+                });
+            }
+        }
+
+        extend_multicollision(f, &mut multicollision)?;
+    }
+}
+
+pub fn forge_expandable_message<C: Compression>(
+    hash: &MdHash<C>,
+    state: &[u8],
+    k: usize,
+) -> Result<ExpandableMessage> {
+    let dummy_block = vec![0; hash.block_size()];
+    let mut expandable_message = ExpandableMessage {
+        block_pairs: Vec::with_capacity(k),
+        compression_calls: 0,
+        state: state.to_vec(),
+    };
+
+    for stage in 0..k {
+        let dummy_count = 1 << (k - 1 - stage);
+        let mut dummy_state = expandable_message.state.clone();
+
+        for _ in 0..dummy_count {
+            dummy_state = hash.compress(&dummy_state, &dummy_block)?;
+        }
+
+        let collision =
+            forge_compression_collision_between(hash, &expandable_message.state, &dummy_state)?;
+        let (short, last_block) = collision.blocks;
+        let mut long = dummy_block.repeat(dummy_count);
+        long.extend_from_slice(&last_block);
+
+        expandable_message.block_pairs.push((short, long));
+        expandable_message.compression_calls += dummy_count + collision.compression_calls;
+        expandable_message.state = collision.state;
+    }
+
+    Ok(expandable_message)
+}
+
+pub fn forge_second_preimage<C: Compression>(
+    hash: &MdHash<C>,
+    message: &[u8],
+    k: usize,
+) -> Result<SecondPreimage> {
+    let block_size = hash.block_size();
+    let block_count = message.len() / block_size;
+
+    if block_count < k + 1 {
+        bail!("message too short for an expandable message of this size")
+    }
+
+    // Remember the state after each block that a bridge could land on, leaving room for
+    // the expandable message in front of the bridge block
+    let mut intermediate_states = HashMap::new();
+    let mut state = hash.initial_state.clone();
+    let mut compression_calls = 0;
+
+    for (index, block) in message[..block_count * block_size]
+        .chunks(block_size)
+        .enumerate()
+    {
+        state = hash.compress(&state, block)?;
+        compression_calls += 1;
+
+        if index >= k && index < k + (1 << k) {
+            intermediate_states
+                .entry(state.clone())
+                .or_insert(index + 1);
+        }
+    }
+
+    let expandable_message = forge_expandable_message(hash, &hash.initial_state, k)?;
+    compression_calls += expandable_message.compression_calls;
+
+    let mut rng = rand::thread_rng();
+
+    loop {
+        let bridge: Vec<u8> = rng.sample_iter(&Standard).take(block_size).collect();
+        let bridge_state = hash.compress(&expandable_message.state, &bridge)?;
+        compression_calls += 1;
+
+        if let Some(&bridged_blocks) = intermediate_states.get(&bridge_state) {
+            let mut forged_message = expandable_message.message(bridged_blocks - 1)?;
+            forged_message.extend_from_slice(&bridge);
+            forged_message.extend_from_slice(&message[bridged_blocks * block_size..]);
+
+            if forged_message != message {
+                return Ok(SecondPreimage {
+                    compression_calls,
+                    message: forged_message,
+                });
+            }
+        }
+    }
+}
+
+pub fn forge_diamond_structure<C: Compression>(
+    hash: &MdHash<C>,
+    k: usize,
+) -> Result<DiamondStructure> {
+    if k == 0 {
+        bail!("diamond structure needs at least one level")
+    }
+
+    let mut rng = rand::thread_rng();
+    let mut states: Vec<Vec<u8>> = (0..1 << k)
+        .map(|_| rng.sample_iter(&Standard).take(hash.state_size()).collect())
+        .collect();
+    let mut diamond_structure = DiamondStructure {
+        compression_calls: 0,
+        levels: Vec::with_capacity(k),
+        root: Vec::new(),
+    };
+
+    // Pair up the states on each level and collide each pair into a single state, halving
+    // the number of states until only the root is left
+    while states.len() > 1 {
+        let mut nodes = Vec::with_capacity(states.len());
+        let mut next_states = Vec::with_capacity(states.len() / 2);
+
+        for pair in states.chunks(2) {
+            let collision = forge_compression_collision_between(hash, &pair[0], &pair[1])?;
+            let (first_block, second_block) = collision.blocks;
+
+            nodes.push((pair[0].clone(), first_block));
+            nodes.push((pair[1].clone(), second_block));
+            next_states.push(collision.state);
+            diamond_structure.compression_calls += collision.compression_calls;
+        }
+
+        diamond_structure.levels.push(nodes);
+        states = next_states;
+    }
+
+    diamond_structure.root = states.remove(0);
+
+    Ok(diamond_structure)
+}
+
+pub fn forge_herded_message<C: Compression>(
+    hash: &MdHash<C>,
+    diamond_structure: &DiamondStructure,
+    prefix: &[u8],
+) -> Result<Vec<u8>> {
+    // A structure built for another hash would never be reached, so the search below
+    // would run forever, and a tampered one would herd to the wrong hash
+    if !diamond_structure.is_valid(hash) {
+        bail!("diamond structure does not match the hash")
+    }
+
+    let leaves: HashMap<&[u8], usize> = diamond_structure.levels[0]
+        .iter()
+        .enumerate()
+        .map(|(index, (state, _))| (&state[..], index))
+        .collect();
+
+    let prefix_state = hash.compress_blocks(&hash.initial_state, prefix)?;
+    let mut rng = rand::thread_rng();
+
+    // Find a linking block from the prefix into any leaf, then follow the tree to the root
+    loop {
+        let link: Vec<u8> = rng.sample_iter(&Standard).take(hash.block_size()).collect();
+        let link_state = hash.compress(&prefix_state, &link)?;
+
+        if let Some(&leaf) = leaves.get(&link_state[..]) {
+            let mut message = prefix.to_vec();
+            message.extend_from_slice(&link);
+
+            let mut index = leaf;
+
+            for nodes in &diamond_structure.levels {
+                message.extend_from_slice(&nodes[index].1);
+                index /= 2;
+            }
+
+            return Ok(message);
+        }
+    }
+}
+
+pub fn forge_md4_collision() -> Result<Md4Collision> {
+    let mut rng = rand::thread_rng();
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+
+        let mut words = [0u32; 16];
+
+        for word in words.iter_mut() {
+            *word = rng.gen();
+        }
+
+        // Round 1: every step output can be forced straight into shape by solving for the
+        // message word that produces it
+        let mut states = md4::initial_step_states(&md4::INITIAL_STATE);
+
+        for (index, conditions) in WANG_ROUND_1_CONDITIONS.iter().enumerate() {
+            let value =
+                apply_md4_conditions(md4::step(&states, &words, index), conditions, &states);
+            words[index] = md4::step_word(&states, value, index);
+            states.push(value);
+        }
+
+        // Round 2: a5 comes from m0 and d5 from m4, so change those words and re-solve the
+        // next four round 1 words to keep the rest of round 1 where it was
+        for &(index, conditions, round_1_index) in &[
+            (16, &WANG_A5_CONDITIONS[..], 0),
+            (17, &WANG_D5_CONDITIONS[..], 4),
+        ] {
+            let value = md4::step(&states, &words, index);
+            let target = apply_md4_conditions(value, conditions, &states);
+
+            if target != value {
+                let mut modified_words = words;
+                modified_words[round_1_index] = md4::step_word(&states, target, index);
+
+                if let Some(modified_states) =
+                    rebuild_md4_round_1(&states, &mut modified_words, round_1_index)
+                {
+                    words = modified_words;
+                    states = modified_states;
+                }
+            }
+
+            let value = md4::step(&states, &words, index);
+            states.push(value);
+        }
+
+        let mut colliding_words = words;
+        colliding_words[1] = colliding_words[1].wrapping_add(1 << 31);
+        colliding_words[2] = colliding_words[2]
+            .wrapping_add(1 << 31)
+            .wrapping_sub(1 << 28);
+        colliding_words[12] = colliding_words[12].wrapping_sub(1 << 16);
+
+        if md4::compress(&md4::INITIAL_STATE, &words)
+            == md4::compress(&md4::INITIAL_STATE, &colliding_words)
+        {
+            return Ok(Md4Collision {
+                attempts,
+                messages: (
+                    md4::block_from_words(&words),
+                    md4::block_from_words(&colliding_words),
+                ),
+            });
+        }
+    }
+}
+
+// A new generator and key pair under which an existing signature also verifies. With
+// R = u1 * G + u2 * Q from verification, any d' gives G' = R / (u1 + u2 * d') and Q' = d' * G'
+pub fn forge_ecdsa_key_for_signature(
+    public_key: &ecdsa::PublicKey,
+    message: &[u8],
+    signature: &dsa::Signature,
+) -> Result<Ecdsa> {
+    let EcdsaParameters {
+        ref curve,
+        ref g,
+        ref n,
+    } = public_key.parameters;
+
+    if !public_key.verify(message, signature) {
+        bail!("signature does not verify")
+    }
+
+    let w = number::invmod(&signature.s, n)?;
+    let u1 = (ecdsa::message_hash(message, n) * &w) % n;
+    let u2 = (&signature.r * &w) % n;
+    let r_point = curve.add(&curve.scale(g, &u1), &curve.scale(&public_key.q, &u2));
+    let mut rng = rand::thread_rng();
+
+    loop {
+        let d = rng.gen_biguint_range(&BigUint::one(), n);
+        let t_inverse = match number::invmod(&((&u1 + &u2 * &d) % n), n) {
+            Ok(t_inverse) => t_inverse,
+            Err(_) => continue,
+        };
+        let forged_g = curve.scale(&r_point, &t_inverse);
+        let q = curve.scale(&forged_g, &d);
+
+        return Ok(Ecdsa {
+            parameters: EcdsaParameters::new(curve.clone(), forged_g, n.clone()),
+            d,
+            q,
+        });
+    }
+}
+
+// A new RSA key pair under which an existing signature also verifies. N' = pq with p - 1
+// and q - 1 smooth makes e' = log_s(pad(m)) mod N' easy to find with Pohlig-Hellman
+pub fn forge_rsa_key_for_signature(
+    public_key: &rsa::PublicKey,
+    message: &[u8],
+    signature: &[u8],
+    algorithm: HashAlgorithm,
+) -> Result<Rsa> {
+    if !public_key.verify(message, signature, algorithm) {
+        bail!("signature does not verify")
+    }
+
+    let bits = public_key.n.bits();
+    let s = BigUint::from_bytes_be(signature);
+    let block = rsa::pkcs1_v15_signature_block(message, algorithm, public_key.byte_len())?;
+    let padded = BigUint::from_bytes_be(&block);
+    let two = BigUint::from(2u32);
+
+    loop {
+        // s must generate all of Z_p* and Z_q* so pad(m) is sure to be a power of it
+        let (p, p_factors) = number::generate_smooth_prime(bits / 2, SMOOTH_FACTOR_BITS, &[])?;
+
+        if !is_generator(&s, &p, &p_factors) {
+            continue;
+        }
+
+        let (q, q_factors) =
+            number::generate_smooth_prime(bits - bits / 2, SMOOTH_FACTOR_BITS, &p_factors[1..])?;
+        let n = &p * &q;
+
+        if n.bits() != bits || n <= s || !is_generator(&s, &q, &q_factors) {
+            continue;
+        }
+
+        let e_p = signature_exponent(&s, &padded, &p, &p_factors)?;
+        let e_q = signature_exponent(&s, &padded, &q, &q_factors)?;
+
+        // p - 1 and q - 1 only share the factor 2, so e' exists when the logs agree mod 2
+        let (e_p, e_q) = match (e_p, e_q) {
+            (Some(e_p), Some(e_q)) if e_p.is_even() == e_q.is_even() => (e_p, e_q),
+            _ => continue,
+        };
+        let half_p_minus_one = (&p - 1u32) / &two;
+        let half_q_minus_one = (&q - 1u32) / &two;
+        let (e, _) = number::crt(&[
+            (&e_p % &two, two.clone()),
+            (e_p % &half_p_minus_one, half_p_minus_one),
+            (e_q % &half_q_minus_one, half_q_minus_one),
+        ])?;
+
+        let totient = (&p - 1u32).lcm(&(&q - 1u32));
+        let d = match number::invmod(&e, &totient) {
+            Ok(d) => d,
+            Err(_) => continue,
+        };
+
+        return Ok(Rsa {
+            d,
+            e,
+            n,
+            q_inverse: number::invmod(&q, &p)?,
+            p,
+            q,
+        });
+    }
+}
+
+// With H known, any message under the same nonce reveals the tag mask, which then
+// authenticates whatever associated data and ciphertext we like. Truncated tags work too,
+// since the forged tag only needs to agree on the bytes that are kept
+pub fn forge_gcm_message(
+    h: Element,
+    known: &GcmMessage,
+    aad: &[u8],
+    ciphertext: &[u8],
+) -> Result<GcmMessage> {
+    let mask = Element::from_bytes(&known.tag)? + gf128::ghash(h, &known.aad, &known.ciphertext)?;
+    let mut tag = (gf128::ghash(h, aad, ciphertext)? + mask).to_bytes();
+    tag.truncate(known.tag.len());
+
+    Ok(GcmMessage {
+        aad: aad.to_vec(),
+        ciphertext: ciphertext.to_vec(),
+        tag,
+    })
+}
+
+fn apply_md4_conditions(value: u32, conditions: &[Md4Condition], states: &[u32]) -> u32 {
+    conditions
+        .iter()
+        .fold(value, |value, condition| match *condition {
+            Md4Condition::Zero(bit) => value & !(1 << (bit - 1)),
+            Md4Condition::One(bit) => value | 1 << (bit - 1),
+            Md4Condition::Equal(bit, back) => {
+                let mask = 1 << (bit - 1);
+                (value & !mask) | (states[states.len() - back] & mask)
+            }
+        })
+}
+
+// Recompute round 1 with a changed word at `changed_index`, re-solving the four words
+// after it so their outputs stay put, and give up if a round 1 condition breaks
+fn rebuild_md4_round_1(
+    states: &[u32],
+    words: &mut [u32; 16],
+    changed_index: usize,
+) -> Option<Vec<u32>> {
+    let mut modified_states = states[..20].to_vec();
+    modified_states[changed_index + 4] =
+        md4::step(&states[..changed_index + 4], words, changed_index);
+
+    for index in changed_index + 1..changed_index + 5 {
+        words[index] = md4::step_word(&modified_states[..index + 4], states[index + 4], index);
+    }
+
+    for (index, conditions) in WANG_ROUND_1_CONDITIONS.iter().enumerate() {
+        let value = modified_states[index + 4];
+
+        if apply_md4_conditions(value, conditions, &modified_states[..index + 4]) != value {
+            return None;
+        }
+    }
+
+    modified_states.extend_from_slice(&states[20..]);
+
+    Some(modified_states)
+}
+
+fn is_generator(g: &BigUint, p: &BigUint, factors: &[BigUint]) -> bool {
+    let p_minus_one = p - 1u32;
+
+    !(g % p).is_zero()
+        && factors
+            .iter()
+            .all(|factor| !g.modpow(&(&p_minus_one / factor), p).is_one())
+}
+
+fn signature_exponent(
+    s: &BigUint,
+    padded: &BigUint,
+    p: &BigUint,
+    factors: &[BigUint],
+) -> Result<Option<BigUint>> {
+    let order_factors: Vec<(BigUint, u32)> =
+        factors.iter().map(|factor| (factor.clone(), 1)).collect();
+
+    dlog::pohlig_hellman(
+        &MultiplicativeGroup::new(p),
+        &(s % p),
+        &(padded % p),
+        &order_factors,
+    )
+}
@@ -0,0 +1,229 @@
+use rand::Rng;
+
+use std::ops::{Add, BitXor, Mul};
+
+use gf2::Matrix;
+use utility::error::Result;
+
+pub mod polynomial;
+
+pub static BLOCK_SIZE: usize = 16;
+
+// x^128 + x^7 + x^2 + x + 1, less the x^128 term
+static REDUCTION: u128 = 0x87;
+
+// An element of GF(2^128) with bit i holding the coefficient of x^i. GCM writes blocks
+// with the x^0 coefficient in the high bit of the first byte, so conversion reflects them
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Element(pub u128);
+
+impl Element {
+    pub fn zero() -> Self {
+        Element(0)
+    }
+
+    pub fn one() -> Self {
+        Element(1)
+    }
+
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        Element((u128::from(rng.gen::<u64>()) << 64) | u128::from(rng.gen::<u64>()))
+    }
+
+    // Short blocks are zero padded on the right, as GHASH pads its final blocks
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() > BLOCK_SIZE {
+            bail!("a field element is at most 16 bytes")
+        }
+
+        let mut block = [0; 16];
+        block[..bytes.len()].copy_from_slice(bytes);
+
+        Ok(Element(u128::from_be_bytes(block).reverse_bits()))
+    }
+
+    pub fn to_bytes(self) -> Vec<u8> {
+        self.0.reverse_bits().to_be_bytes().to_vec()
+    }
+
+    // The coefficients of x^0 through x^127, for treating the field as GF(2)^128
+    pub fn to_bits(self) -> Vec<bool> {
+        (0..128).map(|i| (self.0 >> i) & 1 == 1).collect()
+    }
+
+    pub fn from_bits(bits: &[bool]) -> Result<Self> {
+        if bits.len() != 128 {
+            bail!("a field element has 128 bits")
+        }
+
+        Ok(Element(
+            bits.iter()
+                .enumerate()
+                .fold(0, |value, (i, &bit)| value | (u128::from(bit) << i)),
+        ))
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn square(self) -> Element {
+        self * self
+    }
+
+    pub fn pow(self, exponent: u128) -> Element {
+        let mut result = Element::one();
+
+        for bit in (0..128).rev() {
+            result = result.square();
+
+            if (exponent >> bit) & 1 == 1 {
+                result = result * self;
+            }
+        }
+
+        result
+    }
+
+    // The multiplicative group has order 2^128 - 1
+    pub fn inverse(self) -> Result<Element> {
+        if self.is_zero() {
+            bail!("zero has no inverse")
+        }
+
+        Ok(self.pow(u128::MAX - 1))
+    }
+
+    // Squaring is a field automorphism, so every element has exactly one square root
+    pub fn sqrt(self) -> Element {
+        (0..127).fold(self, |root, _| root.square())
+    }
+}
+
+// Addition and subtraction coincide in characteristic 2, both being exclusive or
+impl Add for Element {
+    type Output = Element;
+
+    fn add(self, other: Element) -> Element {
+        Element(self.0.bitxor(other.0))
+    }
+}
+
+impl Mul for Element {
+    type Output = Element;
+
+    fn mul(self, other: Element) -> Element {
+        let mut a = self.0;
+        let mut b = other.0;
+        let mut product = 0;
+
+        while b != 0 {
+            if b & 1 == 1 {
+                product ^= a;
+            }
+
+            b >>= 1;
+
+            let carry = a >> 127;
+            a <<= 1;
+
+            if carry == 1 {
+                a ^= REDUCTION;
+            }
+        }
+
+        Element(product)
+    }
+}
+
+// Multiplication by a fixed h, precomputed a byte at a time as GCM implementations do.
+// Table k holds the product of h with every value of bits 8k through 8k + 7
+pub struct Multiplier {
+    tables: Vec<[u128; 256]>,
+}
+
+impl Multiplier {
+    pub fn new(h: Element) -> Self {
+        let mut tables = vec![[0; 256]; 16];
+
+        for (k, table) in tables.iter_mut().enumerate() {
+            for bit in 0..8 {
+                let product = (h * Element(1 << (8 * k + bit))).0;
+                let step = 1 << bit;
+
+                for byte in step..2 * step {
+                    table[byte] = table[byte - step] ^ product;
+                }
+            }
+        }
+
+        Multiplier { tables }
+    }
+
+    pub fn multiply(&self, x: Element) -> Element {
+        Element(
+            self.tables
+                .iter()
+                .enumerate()
+                .fold(0, |product, (k, table)| {
+                    product ^ table[(x.0 >> (8 * k)) as usize & 0xff]
+                }),
+        )
+    }
+}
+
+// Multiplication by a fixed c is GF(2)-linear, so it has a matrix whose column i is the
+// bits of c * x^i
+pub fn multiplication_matrix(c: Element) -> Result<Matrix> {
+    let columns: Vec<Vec<bool>> = (0..128).map(|i| (c * Element(1 << i)).to_bits()).collect();
+
+    Matrix::from_columns(&columns)
+}
+
+// Squaring is linear too, since (a + b)^2 = a^2 + b^2 in characteristic 2
+pub fn squaring_matrix() -> Result<Matrix> {
+    let columns: Vec<Vec<bool>> = (0..128)
+        .map(|i| Element(1 << i).square().to_bits())
+        .collect();
+
+    Matrix::from_columns(&columns)
+}
+
+// The padded associated data, the padded ciphertext, then their lengths in bits
+pub fn ghash_blocks(aad: &[u8], ciphertext: &[u8]) -> Result<Vec<Element>> {
+    let mut blocks = Vec::with_capacity((aad.len() + ciphertext.len()) / BLOCK_SIZE + 3);
+
+    for block in aad.chunks(BLOCK_SIZE).chain(ciphertext.chunks(BLOCK_SIZE)) {
+        blocks.push(Element::from_bytes(block)?);
+    }
+
+    blocks.push(Element::from_bytes(&length_block(aad, ciphertext))?);
+
+    Ok(blocks)
+}
+
+// Horner's rule over the blocks, so the last block is multiplied by h once. The blocks
+// are hashed as they are read, since messages may run to millions of them
+pub fn ghash(h: Element, aad: &[u8], ciphertext: &[u8]) -> Result<Element> {
+    let multiplier = Multiplier::new(h);
+    let length_block = length_block(aad, ciphertext);
+    let mut sum = Element::zero();
+
+    for block in aad
+        .chunks(BLOCK_SIZE)
+        .chain(ciphertext.chunks(BLOCK_SIZE))
+        .chain(Some(&length_block[..]))
+    {
+        sum = multiplier.multiply(sum + Element::from_bytes(block)?);
+    }
+
+    Ok(sum)
+}
+
+fn length_block(aad: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    let mut length_block = Vec::with_capacity(BLOCK_SIZE);
+    length_block.extend_from_slice(&(aad.len() as u64 * 8).to_be_bytes());
+    length_block.extend_from_slice(&(ciphertext.len() as u64 * 8).to_be_bytes());
+
+    length_block
+}
@@ -0,0 +1,369 @@
+use rand;
+
+use std::ops::{Add, Mul};
+
+use gf128::Element;
+use utility::error::Result;
+
+// Coefficients run from the constant term upwards, with no trailing zeros, so the
+// zero polynomial has no coefficients at all
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Polynomial {
+    pub coefficients: Vec<Element>,
+}
+
+impl Polynomial {
+    pub fn new(mut coefficients: Vec<Element>) -> Self {
+        while coefficients.last().is_some_and(|c| c.is_zero()) {
+            coefficients.pop();
+        }
+
+        Polynomial { coefficients }
+    }
+
+    pub fn zero() -> Self {
+        Polynomial::new(Vec::new())
+    }
+
+    pub fn one() -> Self {
+        Polynomial::new(vec![Element::one()])
+    }
+
+    pub fn monomial(coefficient: Element, degree: usize) -> Self {
+        let mut coefficients = vec![Element::zero(); degree + 1];
+        coefficients[degree] = coefficient;
+
+        Polynomial::new(coefficients)
+    }
+
+    pub fn random(degree_bound: usize) -> Self {
+        let mut rng = rand::thread_rng();
+
+        Polynomial::new(
+            (0..degree_bound)
+                .map(|_| Element::random(&mut rng))
+                .collect(),
+        )
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    pub fn is_one(&self) -> bool {
+        self.coefficients == [Element::one()]
+    }
+
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    pub fn leading_coefficient(&self) -> Element {
+        self.coefficients
+            .last()
+            .cloned()
+            .unwrap_or_else(Element::zero)
+    }
+
+    pub fn evaluate(&self, x: Element) -> Element {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Element::zero(), |sum, &c| sum * x + c)
+    }
+
+    pub fn scale(&self, factor: Element) -> Polynomial {
+        Polynomial::new(self.coefficients.iter().map(|&c| c * factor).collect())
+    }
+
+    pub fn monic(&self) -> Result<Polynomial> {
+        if self.is_zero() {
+            bail!("the zero polynomial cannot be made monic")
+        }
+
+        Ok(self.scale(self.leading_coefficient().inverse()?))
+    }
+
+    // Only the odd powers survive, since 2 = 0
+    pub fn derivative(&self) -> Polynomial {
+        Polynomial::new(
+            self.coefficients
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, &c)| if i % 2 == 1 { c } else { Element::zero() })
+                .collect(),
+        )
+    }
+
+    // Only defined when every odd power is absent, where f(x) = g(x)^2 and the root g
+    // takes the square root of each even coefficient
+    pub fn sqrt(&self) -> Result<Polynomial> {
+        if self
+            .coefficients
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .any(|c| !c.is_zero())
+        {
+            bail!("polynomial is not a square")
+        }
+
+        Ok(Polynomial::new(
+            self.coefficients
+                .iter()
+                .step_by(2)
+                .map(|c| c.sqrt())
+                .collect(),
+        ))
+    }
+
+    pub fn div_rem(&self, divisor: &Polynomial) -> Result<(Polynomial, Polynomial)> {
+        let divisor_degree = match divisor.degree() {
+            Some(degree) => degree,
+            None => bail!("cannot divide by the zero polynomial"),
+        };
+        let inverse = divisor.leading_coefficient().inverse()?;
+        let mut remainder = self.coefficients.clone();
+        let mut quotient = vec![Element::zero(); remainder.len().saturating_sub(divisor_degree)];
+
+        while remainder.len() > divisor_degree {
+            let shift = remainder.len() - 1 - divisor_degree;
+            let factor = remainder[remainder.len() - 1] * inverse;
+            quotient[shift] = factor;
+
+            for (i, &c) in divisor.coefficients.iter().enumerate() {
+                remainder[shift + i] = remainder[shift + i] + c * factor;
+            }
+
+            remainder.pop();
+
+            while remainder.last().is_some_and(|c| c.is_zero()) {
+                remainder.pop();
+            }
+        }
+
+        Ok((Polynomial::new(quotient), Polynomial::new(remainder)))
+    }
+
+    pub fn rem(&self, modulus: &Polynomial) -> Result<Polynomial> {
+        Ok(self.div_rem(modulus)?.1)
+    }
+
+    // Monic, or zero only when both inputs are zero
+    pub fn gcd(&self, other: &Polynomial) -> Result<Polynomial> {
+        let mut a = self.clone();
+        let mut b = other.clone();
+
+        while !b.is_zero() {
+            let r = a.rem(&b)?;
+            a = b;
+            b = r;
+        }
+
+        if a.is_zero() {
+            Ok(a)
+        } else {
+            a.monic()
+        }
+    }
+
+    pub fn square_mod(&self, modulus: &Polynomial) -> Result<Polynomial> {
+        (self * self).rem(modulus)
+    }
+
+    // Raises to the field size q = 2^128 by squaring once per bit
+    pub fn frobenius_mod(&self, modulus: &Polynomial) -> Result<Polynomial> {
+        let mut result = self.rem(modulus)?;
+
+        for _ in 0..128 {
+            result = result.square_mod(modulus)?;
+        }
+
+        Ok(result)
+    }
+}
+
+impl Add for &Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, other: &Polynomial) -> Polynomial {
+        let (longer, shorter) = if self.coefficients.len() >= other.coefficients.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut coefficients = longer.coefficients.clone();
+
+        for (c, &d) in coefficients.iter_mut().zip(&shorter.coefficients) {
+            *c = *c + d;
+        }
+
+        Polynomial::new(coefficients)
+    }
+}
+
+impl Mul for &Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, other: &Polynomial) -> Polynomial {
+        if self.is_zero() || other.is_zero() {
+            return Polynomial::zero();
+        }
+
+        let mut coefficients =
+            vec![Element::zero(); self.coefficients.len() + other.coefficients.len() - 1];
+
+        for (i, &a) in self.coefficients.iter().enumerate() {
+            for (j, &b) in other.coefficients.iter().enumerate() {
+                coefficients[i + j] = coefficients[i + j] + a * b;
+            }
+        }
+
+        Polynomial::new(coefficients)
+    }
+}
+
+// Monic square-free factors paired with their multiplicity. In characteristic 2 the
+// derivative loses every square, so whatever gcd(f, f') leaves behind once the
+// multiplicities below 2 are peeled off is a perfect square, handled by its root
+pub fn square_free_factorization(f: &Polynomial) -> Result<Vec<(Polynomial, usize)>> {
+    let f = f.monic()?;
+    let mut factors = Vec::new();
+    let mut c = f.gcd(&f.derivative())?;
+    let mut w = f.div_rem(&c)?.0;
+    let mut multiplicity = 1;
+
+    while !w.is_one() {
+        let y = w.gcd(&c)?;
+        let factor = w.div_rem(&y)?.0;
+
+        if !factor.is_one() {
+            factors.push((factor, multiplicity));
+        }
+
+        w = y;
+        c = c.div_rem(&w)?.0;
+        multiplicity += 1;
+    }
+
+    if !c.is_one() {
+        for (factor, root_multiplicity) in square_free_factorization(&c.sqrt()?)? {
+            factors.push((factor, 2 * root_multiplicity));
+        }
+    }
+
+    Ok(factors)
+}
+
+// Splits a square-free polynomial into products of all its irreducible factors of each
+// degree d, using that x^(q^d) - x is the product of every monic irreducible whose
+// degree divides d
+pub fn distinct_degree_factorization(f: &Polynomial) -> Result<Vec<(Polynomial, usize)>> {
+    let mut remaining = f.monic()?;
+    let mut factors = Vec::new();
+    let x = Polynomial::monomial(Element::one(), 1);
+    let mut h = x.clone();
+    let mut degree = 1;
+
+    while remaining.degree().unwrap_or(0) >= 2 * degree {
+        h = h.frobenius_mod(&remaining)?;
+
+        let g = remaining.gcd(&(&h + &x))?;
+
+        if !g.is_one() {
+            remaining = remaining.div_rem(&g)?.0;
+            h = h.rem(&remaining)?;
+            factors.push((g, degree));
+        }
+
+        degree += 1;
+    }
+
+    if let Some(remaining_degree) = remaining.degree() {
+        if remaining_degree > 0 {
+            factors.push((remaining, remaining_degree));
+        }
+    }
+
+    Ok(factors)
+}
+
+// Cantor-Zassenhaus for a product of distinct irreducibles all of the given degree. The
+// field has characteristic 2, so instead of raising to (q^d - 1) / 2 a random a(x) is
+// split by its trace a + a^2 + a^4 + ... + a^(2^(128d - 1)), which lands on 0 or 1
+// independently modulo each factor
+pub fn equal_degree_factorization(f: &Polynomial, degree: usize) -> Result<Vec<Polynomial>> {
+    let f = f.monic()?;
+    let f_degree = f.degree().unwrap_or(0);
+
+    if degree == 0 || f_degree % degree != 0 {
+        bail!("polynomial degree is not a multiple of the factor degree")
+    }
+
+    if f_degree == degree {
+        return Ok(vec![f]);
+    }
+
+    loop {
+        let a = Polynomial::random(f_degree);
+        let mut power = a.clone();
+        let mut trace = a;
+
+        for _ in 1..128 * degree {
+            power = power.square_mod(&f)?;
+            trace = &trace + &power;
+        }
+
+        let g = f.gcd(&trace)?;
+
+        if let Some(g_degree) = g.degree() {
+            if g_degree > 0 && g_degree < f_degree {
+                let mut factors = equal_degree_factorization(&g, degree)?;
+                factors.extend(equal_degree_factorization(&f.div_rem(&g)?.0, degree)?);
+
+                return Ok(factors);
+            }
+        }
+    }
+}
+
+// Every monic irreducible factor with its multiplicity
+pub fn factor(f: &Polynomial) -> Result<Vec<(Polynomial, usize)>> {
+    let mut factors = Vec::new();
+
+    for (square_free, multiplicity) in square_free_factorization(f)? {
+        for (product, degree) in distinct_degree_factorization(&square_free)? {
+            for irreducible in equal_degree_factorization(&product, degree)? {
+                factors.push((irreducible, multiplicity));
+            }
+        }
+    }
+
+    Ok(factors)
+}
+
+// The distinct roots, found by splitting gcd(f, x^q - x) into its linear factors
+pub fn roots(f: &Polynomial) -> Result<Vec<Element>> {
+    let f = f.monic()?;
+
+    if f.degree() == Some(0) {
+        return Ok(Vec::new());
+    }
+
+    let x = Polynomial::monomial(Element::one(), 1);
+    let linear_part = f.gcd(&(&x.frobenius_mod(&f)? + &x))?;
+
+    if linear_part.is_one() {
+        return Ok(Vec::new());
+    }
+
+    // Each factor is x + r, whose root is r itself
+    let mut roots: Vec<Element> = equal_degree_factorization(&linear_part, 1)?
+        .into_iter()
+        .map(|factor| factor.coefficients[0])
+        .collect();
+    roots.sort();
+
+    Ok(roots)
+}
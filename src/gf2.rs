@@ -0,0 +1,251 @@
+use std::ops::Add;
+
+use utility::error::Result;
+
+// A dense matrix over GF(2) with each row packed 64 columns to a word, column k sitting
+// in bit k % 64 of word k / 64. Vectors are plain slices of bools
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Matrix {
+    pub row_count: usize,
+    pub column_count: usize,
+    pub rows: Vec<Vec<u64>>,
+}
+
+impl Matrix {
+    pub fn zero(row_count: usize, column_count: usize) -> Self {
+        Matrix {
+            row_count,
+            column_count,
+            rows: vec![vec![0; word_count(column_count)]; row_count],
+        }
+    }
+
+    pub fn identity(size: usize) -> Self {
+        let mut identity = Matrix::zero(size, size);
+
+        for i in 0..size {
+            identity.set(i, i, true);
+        }
+
+        identity
+    }
+
+    pub fn from_rows(rows: &[Vec<bool>]) -> Result<Self> {
+        let column_count = rows.first().map_or(0, |row| row.len());
+        let mut matrix = Matrix::zero(rows.len(), column_count);
+
+        for (i, row) in rows.iter().enumerate() {
+            if row.len() != column_count {
+                bail!("rows must all have the same length")
+            }
+
+            for (j, &value) in row.iter().enumerate() {
+                matrix.set(i, j, value);
+            }
+        }
+
+        Ok(matrix)
+    }
+
+    pub fn from_columns(columns: &[Vec<bool>]) -> Result<Self> {
+        Ok(Matrix::from_rows(columns)?.transpose())
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> bool {
+        (self.rows[row][column / 64] >> (column % 64)) & 1 == 1
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, value: bool) {
+        if value {
+            self.rows[row][column / 64] |= 1 << (column % 64);
+        } else {
+            self.rows[row][column / 64] &= !(1 << (column % 64));
+        }
+    }
+
+    pub fn row(&self, row: usize) -> Vec<bool> {
+        (0..self.column_count)
+            .map(|column| self.get(row, column))
+            .collect()
+    }
+
+    pub fn column(&self, column: usize) -> Vec<bool> {
+        (0..self.row_count)
+            .map(|row| self.get(row, column))
+            .collect()
+    }
+
+    pub fn row_range(&self, start: usize, end: usize) -> Matrix {
+        Matrix {
+            row_count: end - start,
+            column_count: self.column_count,
+            rows: self.rows[start..end].to_vec(),
+        }
+    }
+
+    pub fn transpose(&self) -> Matrix {
+        let mut transpose = Matrix::zero(self.column_count, self.row_count);
+
+        for i in 0..self.row_count {
+            for j in 0..self.column_count {
+                if self.get(i, j) {
+                    transpose.set(j, i, true);
+                }
+            }
+        }
+
+        transpose
+    }
+
+    // Each row of the product is the sum of the rows of other picked out by a row of self
+    pub fn multiply(&self, other: &Matrix) -> Result<Matrix> {
+        if self.column_count != other.row_count {
+            bail!("matrix dimensions do not match")
+        }
+
+        let mut product = Matrix::zero(self.row_count, other.column_count);
+
+        for (product_row, row) in product.rows.iter_mut().zip(&self.rows) {
+            for k in 0..self.column_count {
+                if (row[k / 64] >> (k % 64)) & 1 == 1 {
+                    xor_row(product_row, &other.rows[k]);
+                }
+            }
+        }
+
+        Ok(product)
+    }
+
+    pub fn multiply_vector(&self, vector: &[bool]) -> Result<Vec<bool>> {
+        if self.column_count != vector.len() {
+            bail!("vector length does not match the matrix")
+        }
+
+        Ok((0..self.row_count)
+            .map(|row| {
+                vector
+                    .iter()
+                    .enumerate()
+                    .filter(|&(column, &value)| value && self.get(row, column))
+                    .count()
+                    % 2
+                    == 1
+            })
+            .collect())
+    }
+
+    // Gauss-Jordan elimination, returning the reduced row echelon form and the column
+    // of each pivot in row order
+    pub fn reduced_row_echelon(&self) -> (Matrix, Vec<usize>) {
+        let mut reduced = self.clone();
+        let mut pivots = Vec::new();
+
+        for column in 0..self.column_count {
+            let pivot_row = pivots.len();
+
+            if pivot_row == self.row_count {
+                break;
+            }
+
+            let found = match (pivot_row..self.row_count).find(|&row| reduced.get(row, column)) {
+                Some(found) => found,
+                None => continue,
+            };
+            reduced.rows.swap(pivot_row, found);
+
+            let pivot = reduced.rows[pivot_row].clone();
+
+            for row in 0..self.row_count {
+                if row != pivot_row && reduced.get(row, column) {
+                    xor_row(&mut reduced.rows[row], &pivot);
+                }
+            }
+
+            pivots.push(column);
+        }
+
+        (reduced, pivots)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.reduced_row_echelon().1.len()
+    }
+
+    // A basis for the solutions of Ax = 0, one vector per row. Each free column gives
+    // the basis vector setting that variable to 1 and every other free variable to 0
+    pub fn kernel(&self) -> Matrix {
+        let (reduced, pivots) = self.reduced_row_echelon();
+        let free_columns: Vec<usize> = (0..self.column_count)
+            .filter(|column| !pivots.contains(column))
+            .collect();
+        let mut kernel = Matrix::zero(free_columns.len(), self.column_count);
+
+        for (i, &free_column) in free_columns.iter().enumerate() {
+            kernel.set(i, free_column, true);
+
+            for (row, &pivot) in pivots.iter().enumerate() {
+                if reduced.get(row, free_column) {
+                    kernel.set(i, pivot, true);
+                }
+            }
+        }
+
+        kernel
+    }
+
+    // One solution of Ax = b with the free variables set to 0, or None if there is none.
+    // Every other solution differs from it by a vector in the kernel
+    pub fn solve(&self, b: &[bool]) -> Result<Option<Vec<bool>>> {
+        if self.row_count != b.len() {
+            bail!("vector length does not match the matrix")
+        }
+
+        let mut augmented = Matrix::zero(self.row_count, self.column_count + 1);
+
+        for (row, &value) in b.iter().enumerate() {
+            for column in 0..self.column_count {
+                augmented.set(row, column, self.get(row, column));
+            }
+
+            augmented.set(row, self.column_count, value);
+        }
+
+        let (reduced, pivots) = augmented.reduced_row_echelon();
+
+        if pivots.last() == Some(&self.column_count) {
+            return Ok(None);
+        }
+
+        let mut solution = vec![false; self.column_count];
+
+        for (row, &pivot) in pivots.iter().enumerate() {
+            solution[pivot] = reduced.get(row, self.column_count);
+        }
+
+        Ok(Some(solution))
+    }
+}
+
+impl Add for &Matrix {
+    type Output = Matrix;
+
+    fn add(self, other: &Matrix) -> Matrix {
+        let mut sum = self.clone();
+
+        for (row, other_row) in sum.rows.iter_mut().zip(&other.rows) {
+            xor_row(row, other_row);
+        }
+
+        sum
+    }
+}
+
+fn word_count(column_count: usize) -> usize {
+    column_count.div_ceil(64)
+}
+
+fn xor_row(row: &mut [u64], other: &[u64]) {
+    for (word, &other_word) in row.iter_mut().zip(other) {
+        *word ^= other_word;
+    }
+}
@@ -0,0 +1,162 @@
+use aes;
+use utility::error::Result;
+
+pub trait Compression {
+    fn block_size(&self) -> usize;
+    fn state_size(&self) -> usize;
+    fn compress(&self, state: &[u8], block: &[u8]) -> Result<Vec<u8>>;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AesCompression {
+    pub state_size: usize,
+}
+
+pub struct MdBuilder<C> {
+    compression: C,
+    initial_state: Option<Vec<u8>>,
+    length_padding: bool,
+}
+
+pub struct MdHash<C> {
+    pub compression: C,
+    pub initial_state: Vec<u8>,
+    pub length_padding: bool,
+}
+
+impl AesCompression {
+    pub fn new(output_bits: usize) -> Result<Self> {
+        if output_bits == 0 || aes::padded_len(output_bits, 8) != output_bits || output_bits > 128 {
+            bail!("output size must be a whole number of bytes no larger than an AES block")
+        }
+
+        Ok(AesCompression {
+            state_size: output_bits / 8,
+        })
+    }
+}
+
+impl Compression for AesCompression {
+    fn block_size(&self) -> usize {
+        16
+    }
+
+    fn state_size(&self) -> usize {
+        self.state_size
+    }
+
+    fn compress(&self, state: &[u8], block: &[u8]) -> Result<Vec<u8>> {
+        // The message block keys AES, the zero-extended state is the plaintext, and the
+        // ciphertext is truncated back down to the state size
+        let mut plaintext = state.to_vec();
+        plaintext.resize(16, 0);
+
+        let mut ciphertext = aes::encrypt_ecb_text(&plaintext, block)?;
+        ciphertext.truncate(self.state_size);
+
+        Ok(ciphertext)
+    }
+}
+
+impl<C: Compression> MdBuilder<C> {
+    pub fn new(compression: C) -> Self {
+        MdBuilder {
+            compression,
+            initial_state: None,
+            length_padding: true,
+        }
+    }
+
+    pub fn initial_state(mut self, initial_state: &[u8]) -> Self {
+        self.initial_state = Some(initial_state.to_vec());
+        self
+    }
+
+    pub fn length_padding(mut self, length_padding: bool) -> Self {
+        self.length_padding = length_padding;
+        self
+    }
+
+    pub fn build(self) -> Result<MdHash<C>> {
+        let state_size = self.compression.state_size();
+        let initial_state = match self.initial_state {
+            Some(initial_state) => initial_state,
+            None => (0..state_size).map(|index| 0x01 + index as u8).collect(),
+        };
+
+        if initial_state.len() != state_size {
+            bail!("initial state does not match the compression state size")
+        }
+
+        if self.length_padding && self.compression.block_size() < 9 {
+            bail!("block size too small for length padding")
+        }
+
+        Ok(MdHash {
+            compression: self.compression,
+            initial_state,
+            length_padding: self.length_padding,
+        })
+    }
+}
+
+impl<C: Compression> MdHash<C> {
+    pub fn block_size(&self) -> usize {
+        self.compression.block_size()
+    }
+
+    pub fn state_size(&self) -> usize {
+        self.compression.state_size()
+    }
+
+    pub fn compress(&self, state: &[u8], block: &[u8]) -> Result<Vec<u8>> {
+        if state.len() != self.state_size() || block.len() != self.block_size() {
+            bail!("state or block has the wrong size for the compression function")
+        }
+
+        self.compression.compress(state, block)
+    }
+
+    pub fn compress_blocks(&self, state: &[u8], blocks: &[u8]) -> Result<Vec<u8>> {
+        if aes::padded_len(blocks.len(), self.block_size()) != blocks.len() {
+            bail!("message is not a whole number of blocks")
+        }
+
+        let mut state = state.to_vec();
+
+        for block in blocks.chunks(self.block_size()) {
+            state = self.compress(&state, block)?;
+        }
+
+        Ok(state)
+    }
+
+    pub fn padding(&self, message_len: usize) -> Vec<u8> {
+        let block_size = self.block_size();
+
+        if !self.length_padding {
+            return vec![0; aes::padded_len(message_len, block_size) - message_len];
+        }
+
+        // 0x80, zeros, then the message length in bits as a big-endian u64
+        let mut padding = vec![0x80];
+        padding.resize(
+            aes::padded_len(message_len + 9, block_size) - message_len - 8,
+            0,
+        );
+        padding.extend_from_slice(&(message_len as u64 * 8).to_be_bytes());
+
+        padding
+    }
+
+    pub fn pad(&self, message: &[u8]) -> Vec<u8> {
+        let mut padded = message.to_vec();
+        padded.extend_from_slice(&self.padding(message.len()));
+
+        padded
+    }
+
+    pub fn hash(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.compress_blocks(&self.initial_state, &self.pad(message))
+    }
+}
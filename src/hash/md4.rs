@@ -0,0 +1,133 @@
+pub static INITIAL_STATE: [u32; 4] = [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476];
+pub static ROUND_CONSTANTS: [u32; 3] = [0, 0x5a82_7999, 0x6ed9_eba1];
+pub static SHIFTS: [[u32; 4]; 3] = [[3, 7, 11, 19], [3, 5, 9, 13], [3, 9, 11, 15]];
+pub static WORD_ORDER: [[usize; 16]; 3] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15],
+    [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15],
+];
+
+pub fn f(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (!x & z)
+}
+
+pub fn g(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (x & z) | (y & z)
+}
+
+pub fn h(x: u32, y: u32, z: u32) -> u32 {
+    x ^ y ^ z
+}
+
+pub fn round_function(round: usize, x: u32, y: u32, z: u32) -> u32 {
+    match round {
+        0 => f(x, y, z),
+        1 => g(x, y, z),
+        _ => h(x, y, z),
+    }
+}
+
+// Step outputs are kept in the order they are produced: a0, d0, c0, b0, a1, d1, c1, b1, ...
+// so step i always updates the value four places back using the three most recent
+pub fn initial_step_states(state: &[u32; 4]) -> Vec<u32> {
+    vec![state[0], state[3], state[2], state[1]]
+}
+
+fn step_sum(states: &[u32], words: &[u32; 16], index: usize) -> u32 {
+    let round = index / 16;
+
+    states[index]
+        .wrapping_add(round_function(
+            round,
+            states[index + 3],
+            states[index + 2],
+            states[index + 1],
+        ))
+        .wrapping_add(words[WORD_ORDER[round][index % 16]])
+        .wrapping_add(ROUND_CONSTANTS[round])
+}
+
+pub fn step(states: &[u32], words: &[u32; 16], index: usize) -> u32 {
+    step_sum(states, words, index).rotate_left(SHIFTS[index / 16][index % 4])
+}
+
+// The message word that makes step `index` output `value`, given the earlier outputs
+pub fn step_word(states: &[u32], value: u32, index: usize) -> u32 {
+    let round = index / 16;
+
+    value
+        .rotate_right(SHIFTS[round][index % 4])
+        .wrapping_sub(states[index])
+        .wrapping_sub(round_function(
+            round,
+            states[index + 3],
+            states[index + 2],
+            states[index + 1],
+        ))
+        .wrapping_sub(ROUND_CONSTANTS[round])
+}
+
+pub fn step_states(state: &[u32; 4], words: &[u32; 16]) -> Vec<u32> {
+    let mut states = initial_step_states(state);
+
+    for index in 0..48 {
+        let value = step(&states, words, index);
+        states.push(value);
+    }
+
+    states
+}
+
+pub fn compress(state: &[u32; 4], words: &[u32; 16]) -> [u32; 4] {
+    let states = step_states(state, words);
+
+    [
+        state[0].wrapping_add(states[48]),
+        state[1].wrapping_add(states[51]),
+        state[2].wrapping_add(states[50]),
+        state[3].wrapping_add(states[49]),
+    ]
+}
+
+pub fn words_from_block(block: &[u8; 64]) -> [u32; 16] {
+    let mut words = [0; 16];
+
+    for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+
+    words
+}
+
+pub fn block_from_words(words: &[u32; 16]) -> Vec<u8> {
+    words
+        .iter()
+        .flat_map(|word| word.to_le_bytes().to_vec())
+        .collect()
+}
+
+pub fn padding(message_len: usize) -> Vec<u8> {
+    // 0x80, zeros, then the message length in bits as a little-endian u64
+    let mut padding = vec![0x80];
+    padding.resize(1 + (119 - message_len % 64) % 64, 0);
+    padding.extend_from_slice(&(message_len as u64 * 8).to_le_bytes());
+
+    padding
+}
+
+pub fn md4(message: &[u8]) -> Vec<u8> {
+    let mut padded = message.to_vec();
+    padded.extend_from_slice(&padding(message.len()));
+
+    let state = padded.chunks_exact(64).fold(INITIAL_STATE, |state, block| {
+        let mut bytes = [0; 64];
+        bytes.copy_from_slice(block);
+
+        compress(&state, &words_from_block(&bytes))
+    });
+
+    state
+        .iter()
+        .flat_map(|word| word.to_le_bytes().to_vec())
+        .collect()
+}
@@ -0,0 +1,2 @@
+pub mod md;
+pub mod md4;
@@ -0,0 +1,157 @@
+use num_rational::BigRational;
+use num_traits::{One, Signed, Zero};
+
+use std::mem;
+
+use utility::error::Result;
+
+pub fn inner_product(first: &[BigRational], second: &[BigRational]) -> BigRational {
+    first
+        .iter()
+        .zip(second)
+        .fold(BigRational::zero(), |sum, (a, b)| sum + a * b)
+}
+
+// The orthogonalized basis b*, without normalization
+pub fn gram_schmidt(basis: &[Vec<BigRational>]) -> Vec<Vec<BigRational>> {
+    let mut orthogonal: Vec<Vec<BigRational>> = Vec::with_capacity(basis.len());
+
+    for vector in basis {
+        let mut projected = vector.clone();
+
+        for previous in &orthogonal {
+            let norm = inner_product(previous, previous);
+
+            if norm.is_zero() {
+                continue;
+            }
+
+            let mu = inner_product(vector, previous) / norm;
+
+            for (value, component) in projected.iter_mut().zip(previous) {
+                *value -= &mu * component;
+            }
+        }
+
+        orthogonal.push(projected);
+    }
+
+    orthogonal
+}
+
+// Size reduced with |mu_ij| <= 1/2, and satisfying the Lovasz condition for delta
+pub fn is_lll_reduced(basis: &[Vec<BigRational>], delta: &BigRational) -> bool {
+    let orthogonal = gram_schmidt(basis);
+    let half = BigRational::new(One::one(), 2.into());
+    let norms: Vec<BigRational> = orthogonal
+        .iter()
+        .map(|vector| inner_product(vector, vector))
+        .collect();
+
+    for i in 0..basis.len() {
+        for j in 0..i {
+            if norms[j].is_zero() {
+                return false;
+            }
+
+            let mu = inner_product(&basis[i], &orthogonal[j]) / &norms[j];
+
+            if mu.abs() > half {
+                return false;
+            }
+
+            if j + 1 == i && norms[i] < (delta - &mu * &mu) * &norms[j] {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+// Lenstra-Lenstra-Lovasz reduction in exact arithmetic, keeping the Gram-Schmidt
+// coefficients mu and squared norms up to date through each reduction and swap rather
+// than recomputing them. The basis vectors must be linearly independent
+pub fn lll(basis: &[Vec<BigRational>], delta: &BigRational) -> Result<Vec<Vec<BigRational>>> {
+    let quarter = BigRational::new(One::one(), 4.into());
+
+    if *delta <= quarter || *delta > BigRational::one() {
+        bail!("delta must lie in (1/4, 1]")
+    }
+
+    let mut basis = basis.to_vec();
+    let n = basis.len();
+    let orthogonal = gram_schmidt(&basis);
+    let mut norms: Vec<BigRational> = orthogonal
+        .iter()
+        .map(|vector| inner_product(vector, vector))
+        .collect();
+
+    if norms.iter().any(|norm| norm.is_zero()) {
+        bail!("basis vectors are linearly dependent")
+    }
+
+    let mut mu = vec![vec![BigRational::zero(); n]; n];
+
+    for i in 0..n {
+        for j in 0..i {
+            mu[i][j] = inner_product(&basis[i], &orthogonal[j]) / &norms[j];
+        }
+    }
+
+    let half = BigRational::new(One::one(), 2.into());
+    let mut k = 1;
+
+    while k < n {
+        for j in (0..k).rev() {
+            if mu[k][j].abs() <= half {
+                continue;
+            }
+
+            let q = mu[k][j].round();
+            let (reduced, rest) = basis.split_at_mut(k);
+
+            for (value, component) in rest[0].iter_mut().zip(&reduced[j]) {
+                *value -= &q * component;
+            }
+
+            let (mu_lower, mu_upper) = mu.split_at_mut(k);
+
+            for (l, value) in mu_upper[0].iter_mut().enumerate().take(j) {
+                *value -= &q * &mu_lower[j][l];
+            }
+
+            mu[k][j] -= &q;
+        }
+
+        if norms[k] >= (delta - &mu[k][k - 1] * &mu[k][k - 1]) * &norms[k - 1] {
+            k += 1;
+            continue;
+        }
+
+        // Swap b_k and b_(k-1), then patch up mu and the norms to match
+        let mu_swapped = mu[k][k - 1].clone();
+        let norm = &norms[k] + &mu_swapped * &mu_swapped * &norms[k - 1];
+        mu[k][k - 1] = &mu_swapped * &norms[k - 1] / &norm;
+        norms[k] = &norms[k - 1] * &norms[k] / &norm;
+        norms[k - 1] = norm;
+        basis.swap(k, k - 1);
+
+        for j in 0..k - 1 {
+            let (mu_lower, mu_upper) = mu.split_at_mut(k);
+            mem::swap(&mut mu_lower[k - 1][j], &mut mu_upper[0][j]);
+        }
+
+        let mu_updated = mu[k][k - 1].clone();
+
+        for row in mu.iter_mut().skip(k + 1) {
+            let t = row[k].clone();
+            row[k] = &row[k - 1] - &mu_swapped * &t;
+            row[k - 1] = t + &mu_updated * &row[k];
+        }
+
+        k = if k > 1 { k - 1 } else { 1 };
+    }
+
+    Ok(basis)
+}
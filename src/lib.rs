@@ -1,16 +1,36 @@
 pub mod aes;
 pub mod analyzer;
+pub mod bank;
 pub mod cookie;
 pub mod decryptor;
+pub mod dh;
+pub mod dlog;
+pub mod dsa;
+pub mod ec;
+pub mod forger;
+pub mod gf128;
+pub mod gf2;
+pub mod hash;
+pub mod lattice;
 pub mod oracle;
+pub mod rc4;
+pub mod rsa;
 pub mod set_1;
 pub mod set_2;
+pub mod set_6;
+pub mod set_7;
+pub mod set_8;
 pub mod utility;
 
 extern crate base64;
 #[macro_use]
 extern crate error_chain;
 extern crate hex;
+extern crate miniz_oxide;
+extern crate num_bigint;
+extern crate num_integer;
+extern crate num_rational;
+extern crate num_traits;
 extern crate openssl;
 extern crate rand;
 extern crate serde;
@@ -1,9 +1,24 @@
 use base64;
+use miniz_oxide::deflate;
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use openssl::sha;
 use rand::{self, distributions::Standard, Rng};
 
-use aes;
+use std::collections::HashSet;
+
+use aes::{self, GcmMessage};
 use analyzer::Mode;
+use dh::{self, Dh, DhParameters};
+use dsa::Signature;
+use ec::ecdh::{self, Ecdh, EcdhParameters, LadderEcdh, LadderEcdhParameters};
+use ec::ecdsa::{self, Ecdsa, EcdsaParameters};
+use ec::weierstrass::Point;
+use rc4;
+use rsa::{self, PublicKey, Rsa};
 use utility::error::{Result, ResultExt};
+use utility::number;
 
 pub struct Oracle {
     pub append_vec: Option<Vec<u8>>,
@@ -14,6 +29,57 @@ pub struct Oracle {
     pub random_prepend: Option<Vec<u8>>,
 }
 
+pub struct BiasedNonceEcdsaOracle {
+    pub ecdsa: Ecdsa,
+    pub zero_bits: usize,
+}
+
+pub struct CompressionOracle {
+    pub block_size: usize,
+    pub mode: Mode,
+    pub rng: rand::ThreadRng,
+    pub session_id: String,
+}
+
+pub struct DhMacOracle {
+    pub dh: Dh,
+    pub message: Vec<u8>,
+}
+
+pub struct EcdhLadderOracle {
+    pub ecdh: LadderEcdh,
+    pub message: Vec<u8>,
+}
+
+pub struct EcdhMacOracle {
+    pub ecdh: Ecdh,
+    pub message: Vec<u8>,
+    pub validate_public_keys: bool,
+}
+
+pub struct GcmOracle {
+    pub key: Vec<u8>,
+    pub nonce: Vec<u8>,
+    pub tag_len: usize,
+}
+
+pub struct Rc4CookieOracle {
+    pub cookie: Vec<u8>,
+}
+
+pub struct RsaPaddingOracle {
+    pub rsa: Rsa,
+}
+
+pub struct RsaParityOracle {
+    pub rsa: Rsa,
+}
+
+pub struct UnpaddedRsaOracle {
+    pub rsa: Rsa,
+    pub seen_hashes: HashSet<[u8; 32]>,
+}
+
 impl Oracle {
     pub fn new() -> Self {
         let block_size = 16;
@@ -124,3 +190,279 @@ impl Oracle {
         aes::encrypt_ecb_text(&mangled_text, &self.key)
     }
 }
+
+impl BiasedNonceEcdsaOracle {
+    pub fn new(parameters: EcdsaParameters, zero_bits: usize) -> Self {
+        BiasedNonceEcdsaOracle {
+            ecdsa: Ecdsa::new(parameters),
+            zero_bits,
+        }
+    }
+
+    pub fn public_key(&self) -> ecdsa::PublicKey {
+        self.ecdsa.public_key()
+    }
+
+    // Bob's nonces always have their low zero_bits bits cleared
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        let mut rng = rand::thread_rng();
+
+        loop {
+            let k = rng.gen_biguint_range(&BigUint::one(), &self.ecdsa.parameters.n)
+                >> self.zero_bits
+                << self.zero_bits;
+
+            if k.is_zero() {
+                continue;
+            }
+
+            if let Ok(signature) = self.ecdsa.sign_with_nonce(message, &k) {
+                return signature;
+            }
+        }
+    }
+}
+
+impl CompressionOracle {
+    pub fn new(session_id: &str, mode: Mode) -> Self {
+        CompressionOracle {
+            block_size: 16,
+            mode,
+            rng: rand::thread_rng(),
+            session_id: String::from(session_id),
+        }
+    }
+
+    pub fn format_request(&self, plaintext: &[u8]) -> Vec<u8> {
+        let mut request = format!(
+            "POST / HTTP/1.1\nHost: hapless.com\nCookie: sessionid={}\nContent-Length: {}\n",
+            self.session_id,
+            plaintext.len()
+        )
+        .into_bytes();
+        request.extend_from_slice(plaintext);
+
+        request
+    }
+
+    pub fn compressed_encrypted_len(&mut self, plaintext: &[u8]) -> Result<usize> {
+        let mut compressed_request = deflate::compress_to_vec(&self.format_request(plaintext), 6);
+        // A fresh key and IV or nonce for every request
+        let key = aes::generate_random_aes_key(&mut self.rng, self.block_size);
+
+        let ciphertext = match self.mode {
+            Mode::Cbc => {
+                let iv = aes::generate_random_aes_key(&mut self.rng, self.block_size);
+                aes::pkcs7_pad_vec(&mut compressed_request, self.block_size)?;
+                aes::encrypt_cbc_text(&compressed_request, &key, &iv)?
+            }
+            Mode::Ctr => aes::ctr_text(&compressed_request, &key, self.rng.gen())?,
+            Mode::Ecb | Mode::None => bail!("compression oracle only supports CBC and CTR"),
+        };
+
+        Ok(ciphertext.len())
+    }
+}
+
+impl DhMacOracle {
+    pub fn new(parameters: DhParameters) -> Self {
+        DhMacOracle {
+            dh: Dh::new(parameters),
+            message: b"crazy flamboyant for the rap enjoyment".to_vec(),
+        }
+    }
+
+    pub fn public_key(&self) -> BigUint {
+        self.dh.y.clone()
+    }
+
+    // Bob takes whatever public key he is handed and MACs his message with the result
+    pub fn respond(&self, public_key: &BigUint) -> Result<(Vec<u8>, Vec<u8>)> {
+        let shared_secret = self.dh.shared_secret(public_key);
+        let mac = dh::mac(&shared_secret, &self.message)?;
+
+        Ok((self.message.clone(), mac))
+    }
+}
+
+impl EcdhLadderOracle {
+    pub fn new(parameters: LadderEcdhParameters) -> Self {
+        EcdhLadderOracle {
+            ecdh: LadderEcdh::new(parameters),
+            message: b"crazy flamboyant for the rap enjoyment".to_vec(),
+        }
+    }
+
+    pub fn new_with_private_key_bound(parameters: LadderEcdhParameters, bound: &BigUint) -> Self {
+        EcdhLadderOracle {
+            ecdh: LadderEcdh::new_with_private_key_bound(parameters, bound),
+            message: b"crazy flamboyant for the rap enjoyment".to_vec(),
+        }
+    }
+
+    pub fn public_key(&self) -> BigUint {
+        self.ecdh.public_key.clone()
+    }
+
+    // Bob runs his ladder on any u at all, including u coordinates from the twist
+    pub fn respond(&self, public_key: &BigUint) -> Result<(Vec<u8>, Vec<u8>)> {
+        let shared_secret = self.ecdh.shared_secret(public_key);
+        let mac = dh::mac(&shared_secret, &self.message)?;
+
+        Ok((self.message.clone(), mac))
+    }
+}
+
+impl EcdhMacOracle {
+    pub fn new(parameters: EcdhParameters, validate_public_keys: bool) -> Self {
+        EcdhMacOracle {
+            ecdh: Ecdh::new(parameters),
+            message: b"crazy flamboyant for the rap enjoyment".to_vec(),
+            validate_public_keys,
+        }
+    }
+
+    pub fn public_key(&self) -> Point {
+        self.ecdh.y.clone()
+    }
+
+    // Unless told to validate, Bob never checks that the point is on his curve
+    pub fn respond(&self, public_key: &Point) -> Result<(Vec<u8>, Vec<u8>)> {
+        let parameters = &self.ecdh.parameters;
+
+        if self.validate_public_keys {
+            parameters.curve.validate(public_key, &parameters.n)?;
+        }
+
+        let shared_secret = self.ecdh.shared_secret(public_key);
+        let mac = ecdh::mac(&shared_secret, &parameters.curve.p, &self.message)?;
+
+        Ok((self.message.clone(), mac))
+    }
+}
+
+impl GcmOracle {
+    pub fn new() -> Self {
+        GcmOracle::new_with_tag_len(16)
+    }
+
+    // Tags are truncated to tag_len bytes
+    pub fn new_with_tag_len(tag_len: usize) -> Self {
+        let mut rng = rand::thread_rng();
+
+        GcmOracle {
+            key: aes::generate_random_aes_key(&mut rng, 16),
+            nonce: rng.sample_iter(&Standard).take(12).collect(),
+            tag_len,
+        }
+    }
+
+    // The nonce is fixed when the oracle is made, so every message reuses it
+    pub fn encrypt(&self, plaintext: &[u8], aad: &[u8]) -> Result<GcmMessage> {
+        let mut message = aes::encrypt_gcm_text(plaintext, aad, &self.key, &self.nonce)?;
+        message.tag.truncate(self.tag_len);
+
+        Ok(message)
+    }
+
+    pub fn decrypt(&self, message: &GcmMessage) -> Result<Vec<u8>> {
+        aes::decrypt_gcm_text(message, &self.key, &self.nonce)
+    }
+}
+
+impl Default for GcmOracle {
+    fn default() -> Self {
+        GcmOracle::new()
+    }
+}
+
+impl Rc4CookieOracle {
+    pub fn new(cookie: &[u8]) -> Self {
+        Rc4CookieOracle {
+            cookie: cookie.to_vec(),
+        }
+    }
+
+    // Takes &self and a per-thread rng so one oracle can be shared across threads
+    pub fn encrypt(&self, prefix: &[u8]) -> Result<Vec<u8>> {
+        let key: Vec<u8> = rand::thread_rng().sample_iter(&Standard).take(16).collect();
+        let mut plaintext = prefix.to_vec();
+        plaintext.extend_from_slice(&self.cookie);
+
+        rc4::rc4_text(&plaintext, &key)
+    }
+}
+
+impl UnpaddedRsaOracle {
+    pub fn new(bits: usize) -> Result<Self> {
+        Ok(UnpaddedRsaOracle {
+            rsa: Rsa::new(bits)?,
+            seen_hashes: HashSet::new(),
+        })
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.rsa.public_key()
+    }
+
+    pub fn encrypt(&self, plaintext: &BigUint) -> BigUint {
+        self.rsa.encrypt(plaintext)
+    }
+
+    pub fn decrypt(&mut self, ciphertext: &BigUint) -> Result<BigUint> {
+        // Each ciphertext may only be decrypted once; a repeat submission is refused. It is
+        // reduced first, since c + n decrypts exactly as c does
+        let ciphertext = ciphertext % &self.rsa.n;
+        let ciphertext_hash = sha::sha256(&ciphertext.to_bytes_be());
+        if !self.seen_hashes.insert(ciphertext_hash) {
+            bail!("ciphertext has already been decrypted")
+        }
+
+        Ok(self.rsa.decrypt(&ciphertext))
+    }
+}
+
+impl RsaParityOracle {
+    pub fn new(bits: usize) -> Result<Self> {
+        Ok(RsaParityOracle {
+            rsa: Rsa::new_with_exponent(bits, &BigUint::from(65537u32))?,
+        })
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.rsa.public_key()
+    }
+
+    pub fn encrypt(&self, plaintext: &BigUint) -> BigUint {
+        self.rsa.encrypt(plaintext)
+    }
+
+    pub fn is_plaintext_even(&self, ciphertext: &BigUint) -> bool {
+        self.rsa.decrypt(ciphertext).is_even()
+    }
+}
+
+impl RsaPaddingOracle {
+    pub fn new(bits: usize) -> Result<Self> {
+        Ok(RsaPaddingOracle {
+            rsa: Rsa::new(bits)?,
+        })
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.rsa.public_key()
+    }
+
+    pub fn encrypt(&self, message: &[u8]) -> Result<BigUint> {
+        let block = rsa::pkcs1_v15_encryption_pad(message, self.public_key().byte_len())?;
+
+        Ok(self.rsa.encrypt(&BigUint::from_bytes_be(&block)))
+    }
+
+    pub fn is_padding_valid(&self, ciphertext: &BigUint) -> bool {
+        let byte_len = self.public_key().byte_len();
+        let block = number::to_padded_bytes(&self.rsa.decrypt(ciphertext), byte_len);
+
+        block[..2] == [0x00, 0x02]
+    }
+}
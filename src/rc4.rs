@@ -0,0 +1,52 @@
+use utility::error::Result;
+
+pub struct Rc4 {
+    i: u8,
+    j: u8,
+    state: [u8; 256],
+}
+
+impl Rc4 {
+    pub fn new(key: &[u8]) -> Result<Self> {
+        if key.is_empty() || key.len() > 256 {
+            bail!("RC4 key must be between 1 and 256 bytes")
+        }
+
+        let mut state = [0; 256];
+
+        for (index, value) in state.iter_mut().enumerate() {
+            *value = index as u8;
+        }
+
+        let mut j: u8 = 0;
+
+        for index in 0..256 {
+            j = j
+                .wrapping_add(state[index])
+                .wrapping_add(key[index % key.len()]);
+            state.swap(index, j as usize);
+        }
+
+        Ok(Rc4 { i: 0, j: 0, state })
+    }
+
+    pub fn keystream_byte(&mut self) -> u8 {
+        self.i = self.i.wrapping_add(1);
+        self.j = self.j.wrapping_add(self.state[self.i as usize]);
+        self.state.swap(self.i as usize, self.j as usize);
+
+        let index = self.state[self.i as usize].wrapping_add(self.state[self.j as usize]);
+        self.state[index as usize]
+    }
+
+    pub fn apply_keystream(&mut self, text_bytes: &[u8]) -> Vec<u8> {
+        text_bytes
+            .iter()
+            .map(|byte| byte ^ self.keystream_byte())
+            .collect()
+    }
+}
+
+pub fn rc4_text(text_bytes: &[u8], key: &[u8]) -> Result<Vec<u8>> {
+    Ok(Rc4::new(key)?.apply_keystream(text_bytes))
+}
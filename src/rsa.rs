@@ -0,0 +1,229 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::One;
+use openssl::sha;
+use rand::{self, Rng};
+
+use utility::error::Result;
+use utility::number;
+
+static SHA1_DIGEST_INFO: [u8; 15] = [
+    0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04, 0x14,
+];
+static SHA256_DIGEST_INFO: [u8; 19] = [
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05,
+    0x00, 0x04, 0x20,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha256,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublicKey {
+    pub e: BigUint,
+    pub n: BigUint,
+}
+
+pub struct Rsa {
+    pub d: BigUint,
+    pub e: BigUint,
+    pub n: BigUint,
+    pub p: BigUint,
+    pub q: BigUint,
+    pub q_inverse: BigUint,
+}
+
+impl HashAlgorithm {
+    pub fn digest(&self, message: &[u8]) -> Vec<u8> {
+        match *self {
+            HashAlgorithm::Sha1 => sha::sha1(message).to_vec(),
+            HashAlgorithm::Sha256 => sha::sha256(message).to_vec(),
+        }
+    }
+
+    pub fn digest_info_prefix(&self) -> &'static [u8] {
+        match *self {
+            HashAlgorithm::Sha1 => &SHA1_DIGEST_INFO,
+            HashAlgorithm::Sha256 => &SHA256_DIGEST_INFO,
+        }
+    }
+
+    pub fn digest_info(&self, message: &[u8]) -> Vec<u8> {
+        let mut digest_info = self.digest_info_prefix().to_vec();
+        digest_info.extend_from_slice(&self.digest(message));
+
+        digest_info
+    }
+}
+
+pub fn pkcs1_v15_signature_block(
+    message: &[u8],
+    algorithm: HashAlgorithm,
+    block_len: usize,
+) -> Result<Vec<u8>> {
+    let digest_info = algorithm.digest_info(message);
+
+    // 00 01 FF .. FF 00 DigestInfo, with at least 8 bytes of FF
+    if block_len < digest_info.len() + 11 {
+        bail!("modulus too short for signature block")
+    }
+
+    let mut block = vec![0x00, 0x01];
+    block.resize(block_len - digest_info.len() - 1, 0xff);
+    block.push(0x00);
+    block.extend_from_slice(&digest_info);
+
+    Ok(block)
+}
+
+pub fn pkcs1_v15_encryption_pad(message: &[u8], block_len: usize) -> Result<Vec<u8>> {
+    // 00 02 PS 00 M, where PS is at least 8 random nonzero bytes
+    if block_len < message.len() + 11 {
+        bail!("message too long for modulus")
+    }
+
+    let mut rng = rand::thread_rng();
+    let mut block = vec![0x00, 0x02];
+
+    while block.len() < block_len - message.len() - 1 {
+        block.push(rng.gen_range(1, 256) as u8);
+    }
+
+    block.push(0x00);
+    block.extend_from_slice(message);
+
+    Ok(block)
+}
+
+pub fn pkcs1_v15_encryption_unpad(block: &[u8]) -> Result<Vec<u8>> {
+    if block.len() < 11 || block[..2] != [0x00, 0x02] {
+        bail!("Invalid padding detected")
+    }
+
+    match block[2..].iter().position(|&byte| byte == 0x00) {
+        Some(index) if index >= 8 => Ok(block[index + 3..].to_vec()),
+        _ => bail!("Invalid padding detected"),
+    }
+}
+
+impl PublicKey {
+    pub fn encrypt(&self, plaintext: &BigUint) -> BigUint {
+        plaintext.modpow(&self.e, &self.n)
+    }
+
+    pub fn byte_len(&self) -> usize {
+        self.n.bits().div_ceil(8)
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &[u8], algorithm: HashAlgorithm) -> bool {
+        let signature = BigUint::from_bytes_be(signature);
+
+        if signature >= self.n {
+            return false;
+        }
+
+        let block = number::to_padded_bytes(&self.encrypt(&signature), self.byte_len());
+
+        match pkcs1_v15_signature_block(message, algorithm, self.byte_len()) {
+            Ok(expected_block) => block == expected_block,
+            Err(_) => false,
+        }
+    }
+
+    pub fn verify_sloppy(&self, message: &[u8], signature: &[u8]) -> bool {
+        let signature = BigUint::from_bytes_be(signature);
+        let block = number::to_padded_bytes(&self.encrypt(&signature), self.byte_len());
+
+        if block.len() < 3 || block[..2] != [0x00, 0x01] {
+            return false;
+        }
+
+        let mut index = 2;
+
+        while index < block.len() && block[index] == 0xff {
+            index += 1;
+        }
+
+        if index == 2 || index == block.len() || block[index] != 0x00 {
+            return false;
+        }
+
+        index += 1;
+
+        for algorithm in &[HashAlgorithm::Sha1, HashAlgorithm::Sha256] {
+            if block[index..].starts_with(algorithm.digest_info_prefix()) {
+                // The digest is read straight after the prefix, never checking that it
+                // ends the block, so anything can trail it
+                let digest = algorithm.digest(message);
+                let digest_start = index + algorithm.digest_info_prefix().len();
+
+                return block[digest_start..].starts_with(&digest);
+            }
+        }
+
+        false
+    }
+}
+
+impl Rsa {
+    pub fn new(bits: usize) -> Result<Self> {
+        Self::new_with_exponent(bits, &BigUint::from(3u32))
+    }
+
+    pub fn new_with_exponent(bits: usize, e: &BigUint) -> Result<Self> {
+        loop {
+            let p = number::generate_prime(bits / 2)?;
+            let q = number::generate_prime(bits - bits / 2)?;
+
+            if p == q {
+                continue;
+            }
+
+            let totient = (&p - BigUint::one()).lcm(&(&q - BigUint::one()));
+
+            // e must be invertible mod the totient, otherwise pick new primes
+            if let Ok(d) = number::invmod(e, &totient) {
+                return Ok(Rsa {
+                    d,
+                    e: e.clone(),
+                    n: &p * &q,
+                    q_inverse: number::invmod(&q, &p)?,
+                    p,
+                    q,
+                });
+            }
+        }
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            e: self.e.clone(),
+            n: self.n.clone(),
+        }
+    }
+
+    pub fn encrypt(&self, plaintext: &BigUint) -> BigUint {
+        plaintext.modpow(&self.e, &self.n)
+    }
+
+    pub fn decrypt(&self, ciphertext: &BigUint) -> BigUint {
+        // Decrypt mod p and mod q separately, then recombine with Garner's formula
+        let plaintext_p = ciphertext.modpow(&(&self.d % (&self.p - 1u32)), &self.p);
+        let plaintext_q = ciphertext.modpow(&(&self.d % (&self.q - 1u32)), &self.q);
+        let difference = (&plaintext_p + &self.p - (&plaintext_q % &self.p)) % &self.p;
+        let h = (difference * &self.q_inverse) % &self.p;
+
+        plaintext_q + h * &self.q
+    }
+
+    pub fn sign(&self, message: &[u8], algorithm: HashAlgorithm) -> Result<Vec<u8>> {
+        let byte_len = self.public_key().byte_len();
+        let block = pkcs1_v15_signature_block(message, algorithm, byte_len)?;
+        let signature = self.decrypt(&BigUint::from_bytes_be(&block));
+
+        Ok(number::to_padded_bytes(&signature, byte_len))
+    }
+}
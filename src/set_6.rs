@@ -0,0 +1,235 @@
+use base64;
+use num_bigint::BigUint;
+use num_traits::{Num, Zero};
+
+use decryptor;
+use dsa;
+use forger;
+use oracle::{RsaPaddingOracle, RsaParityOracle, UnpaddedRsaOracle};
+use rsa::{self, HashAlgorithm, PublicKey, Rsa};
+use utility::error::{Result, ResultExt};
+use utility::file;
+use utility::number;
+
+// Challenge 41
+pub fn create_unpadded_rsa_oracle(bits: usize) -> Result<UnpaddedRsaOracle> {
+    UnpaddedRsaOracle::new(bits)
+}
+
+pub fn encrypt_with_unpadded_rsa_oracle(oracle: &UnpaddedRsaOracle, plaintext: &str) -> Vec<u8> {
+    oracle
+        .encrypt(&BigUint::from_bytes_be(plaintext.as_bytes()))
+        .to_bytes_be()
+}
+
+pub fn decrypt_with_unpadded_rsa_oracle(
+    oracle: &mut UnpaddedRsaOracle,
+    ciphertext: &[u8],
+) -> Result<String> {
+    let plaintext = oracle.decrypt(&BigUint::from_bytes_be(ciphertext))?;
+
+    String::from_utf8(plaintext.to_bytes_be()).chain_err(|| "could not convert vec to utf8 string")
+}
+
+pub fn recover_unpadded_rsa_plaintext(
+    oracle: &mut UnpaddedRsaOracle,
+    ciphertext: &[u8],
+) -> Result<String> {
+    let public_key = oracle.public_key();
+
+    let plaintext = decryptor::break_unpadded_rsa_fn(
+        &BigUint::from_bytes_be(ciphertext),
+        &public_key,
+        &mut |blinded_ciphertext| oracle.decrypt(blinded_ciphertext),
+    )?;
+
+    String::from_utf8(plaintext.to_bytes_be()).chain_err(|| "could not convert vec to utf8 string")
+}
+
+// Challenge 42
+pub fn generate_rsa_key_pair(bits: usize) -> Result<Rsa> {
+    Rsa::new(bits)
+}
+
+pub fn rsa_sign_message(rsa: &Rsa, message: &str, algorithm: HashAlgorithm) -> Result<Vec<u8>> {
+    rsa.sign(message.as_bytes(), algorithm)
+}
+
+pub fn rsa_verify_signature(
+    public_key: &PublicKey,
+    message: &str,
+    signature: &[u8],
+    algorithm: HashAlgorithm,
+) -> bool {
+    public_key.verify(message.as_bytes(), signature, algorithm)
+}
+
+pub fn rsa_sloppy_verify_signature(
+    public_key: &PublicKey,
+    message: &str,
+    signature: &[u8],
+) -> bool {
+    public_key.verify_sloppy(message.as_bytes(), signature)
+}
+
+pub fn forge_rsa_signature(
+    public_key: &PublicKey,
+    message: &str,
+    algorithm: HashAlgorithm,
+) -> Result<Vec<u8>> {
+    forger::forge_e3_rsa_signature(message.as_bytes(), public_key, algorithm)
+}
+
+// Challenge 43
+pub fn dsa_generate_key_pair() -> Result<dsa::Dsa> {
+    Ok(dsa::Dsa::new(dsa::DsaParameters::new()?))
+}
+
+pub fn dsa_sign_message(dsa: &dsa::Dsa, message: &str) -> dsa::Signature {
+    dsa.sign(message.as_bytes())
+}
+
+pub fn dsa_verify_signature(
+    public_key: &dsa::PublicKey,
+    message: &str,
+    signature: &dsa::Signature,
+) -> bool {
+    public_key.verify(message.as_bytes(), signature)
+}
+
+pub fn recover_dsa_private_key_from_nonce_range(
+    y: &str,
+    message: &str,
+    r: &str,
+    s: &str,
+    max_nonce: u64,
+) -> Result<String> {
+    let public_key = dsa::PublicKey {
+        parameters: dsa::DsaParameters::new()?,
+        y: BigUint::from_str_radix(y, 16).chain_err(|| "could not parse y")?,
+    };
+    let signature = dsa::Signature {
+        r: BigUint::from_str_radix(r, 10).chain_err(|| "could not parse r")?,
+        s: BigUint::from_str_radix(s, 10).chain_err(|| "could not parse s")?,
+    };
+    let hash = dsa::message_hash(message.as_bytes());
+
+    let x = decryptor::break_dsa_nonce_range(&public_key, &hash, &signature, max_nonce)?;
+
+    Ok(dsa::private_key_fingerprint(&x))
+}
+
+// Challenge 44
+pub fn recover_dsa_private_key_from_repeated_nonce(
+    file_path: &str,
+    y: &str,
+) -> Result<(String, Vec<(usize, usize)>)> {
+    let signed_messages = file::read_signed_messages(file_path)?;
+    let public_key = dsa::PublicKey {
+        parameters: dsa::DsaParameters::new()?,
+        y: BigUint::from_str_radix(y, 16).chain_err(|| "could not parse y")?,
+    };
+
+    let key_state = decryptor::break_dsa_repeated_nonce(&public_key, &signed_messages)?;
+
+    Ok((
+        dsa::private_key_fingerprint(&key_state.x),
+        key_state.colliding_pairs,
+    ))
+}
+
+// Challenge 45
+pub fn dsa_public_key_with_zero_generator(public_key: &dsa::PublicKey) -> dsa::PublicKey {
+    public_key.with_generator(BigUint::zero())
+}
+
+pub fn dsa_public_key_with_p_plus_one_generator(public_key: &dsa::PublicKey) -> dsa::PublicKey {
+    let g = &public_key.parameters.p + 1u32;
+    public_key.with_generator(g)
+}
+
+pub fn dsa_sloppy_verify_signature(
+    public_key: &dsa::PublicKey,
+    message: &str,
+    signature: &dsa::Signature,
+) -> bool {
+    public_key.verify_sloppy(message.as_bytes(), signature)
+}
+
+pub fn forge_dsa_zero_generator_signature(public_key: &dsa::PublicKey) -> Result<dsa::Signature> {
+    forger::forge_dsa_zero_generator_signature(public_key)
+}
+
+pub fn forge_dsa_magic_signature(public_key: &dsa::PublicKey) -> Result<dsa::Signature> {
+    forger::forge_dsa_magic_signature(public_key)
+}
+
+// Challenge 46
+pub fn create_rsa_parity_oracle(bits: usize) -> Result<RsaParityOracle> {
+    RsaParityOracle::new(bits)
+}
+
+pub fn encrypt_base64_with_rsa_parity_oracle(
+    oracle: &RsaParityOracle,
+    base64_plaintext: &str,
+) -> Result<Vec<u8>> {
+    let plaintext =
+        base64::decode(base64_plaintext).chain_err(|| "could not decode base64 string")?;
+
+    Ok(oracle
+        .encrypt(&BigUint::from_bytes_be(&plaintext))
+        .to_bytes_be())
+}
+
+pub fn recover_rsa_parity_plaintext<F>(
+    oracle: &RsaParityOracle,
+    ciphertext: &[u8],
+    progress_fn: &mut F,
+) -> Result<String>
+where
+    F: FnMut(&[u8]),
+{
+    let public_key = oracle.public_key();
+
+    let plaintext = decryptor::break_rsa_parity_fn(
+        &BigUint::from_bytes_be(ciphertext),
+        &public_key,
+        &mut |trial_ciphertext| Ok(oracle.is_plaintext_even(trial_ciphertext)),
+        Some(&mut |upper_bound: &BigUint| progress_fn(&upper_bound.to_bytes_be())),
+    )?;
+
+    String::from_utf8(plaintext.to_bytes_be()).chain_err(|| "could not convert vec to utf8 string")
+}
+
+// Challenges 47 and 48
+pub fn create_rsa_padding_oracle(bits: usize) -> Result<RsaPaddingOracle> {
+    RsaPaddingOracle::new(bits)
+}
+
+pub fn encrypt_with_rsa_padding_oracle(
+    oracle: &RsaPaddingOracle,
+    plaintext: &str,
+) -> Result<Vec<u8>> {
+    Ok(oracle.encrypt(plaintext.as_bytes())?.to_bytes_be())
+}
+
+pub fn recover_rsa_padded_plaintext(
+    oracle: &RsaPaddingOracle,
+    ciphertext: &[u8],
+) -> Result<(String, usize)> {
+    let public_key = oracle.public_key();
+
+    let (plaintext_block, queries) = decryptor::break_rsa_padding_fn(
+        &BigUint::from_bytes_be(ciphertext),
+        &public_key,
+        &mut |trial_ciphertext| Ok(oracle.is_padding_valid(trial_ciphertext)),
+    )?;
+
+    let padded_block = number::to_padded_bytes(&plaintext_block, public_key.byte_len());
+    let plaintext = rsa::pkcs1_v15_encryption_unpad(&padded_block)?;
+
+    let plaintext_string =
+        String::from_utf8(plaintext).chain_err(|| "could not convert vec to utf8 string")?;
+
+    Ok((plaintext_string, queries))
+}
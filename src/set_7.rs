@@ -0,0 +1,284 @@
+use base64;
+use hex;
+
+use aes;
+use analyzer::Mode;
+use bank::{Bank, TransactionList, Transfer};
+use decryptor;
+use forger::{self, DiamondStructure};
+use hash::md::{AesCompression, MdBuilder, MdHash};
+use hash::md4;
+use oracle::{CompressionOracle, Rc4CookieOracle};
+use utility::error::{Result, ResultExt};
+
+// Challenge 49
+pub fn create_bank() -> Bank {
+    Bank::new()
+}
+
+pub fn bank_transfer_request(bank: &Bank, from: u64, to: u64, amount: u64) -> Result<Vec<u8>> {
+    bank.transfer_request(from, to, amount)
+}
+
+pub fn bank_verify_transfer_request(bank: &Bank, request: &[u8]) -> Result<Transfer> {
+    bank.verify_transfer_request(request)
+}
+
+pub fn forge_transfer_request(request: &[u8], from: u64, block_size: usize) -> Result<Vec<u8>> {
+    if request.len() < 3 * block_size {
+        bail!("request is too short")
+    }
+
+    let (message, iv_mac) = request.split_at(request.len() - 2 * block_size);
+    let (iv, mac) = iv_mac.split_at(block_size);
+
+    // Rewrite the `from` field, which sits wholly inside the first block
+    let first_block = String::from_utf8_lossy(&message[..block_size]).into_owned();
+    let from_end = match first_block.find('&') {
+        Some(index) if first_block.starts_with("from=") => index,
+        _ => bail!("from field is not in the first block"),
+    };
+    let forged_first_block = format!("from={}{}", from, &first_block[from_end..]);
+
+    if forged_first_block.len() != block_size {
+        bail!("forged account id must be the same length as the original")
+    }
+
+    let forged_iv = forger::forge_cbc_mac_iv(message, iv, forged_first_block.as_bytes())?;
+
+    let mut forged_request = Vec::from(forged_first_block);
+    forged_request.extend_from_slice(&message[block_size..]);
+    forged_request.extend_from_slice(&forged_iv);
+    forged_request.extend_from_slice(mac);
+
+    Ok(forged_request)
+}
+
+pub fn bank_transaction_list_request(
+    bank: &Bank,
+    from: u64,
+    transactions: &[(u64, u64)],
+) -> Result<Vec<u8>> {
+    bank.transaction_list_request(from, transactions)
+}
+
+pub fn bank_verify_transaction_list_request(
+    bank: &Bank,
+    request: &[u8],
+) -> Result<TransactionList> {
+    bank.verify_transaction_list_request(request)
+}
+
+pub fn forge_transaction_list_request(
+    bank: &Bank,
+    victim_request: &[u8],
+    attacker: u64,
+    amount: u64,
+) -> Result<Vec<u8>> {
+    let block_size = bank.block_size;
+
+    if victim_request.len() <= block_size {
+        bail!("request is too short")
+    }
+
+    let (victim_message, victim_mac) = victim_request.split_at(victim_request.len() - block_size);
+
+    // The attacker can only get requests from their own account signed. Its first block gets
+    // garbled when spliced onto the victim's message, so lead with a throwaway transaction
+    // that keeps the real one clear of it.
+    let attacker_request =
+        bank.transaction_list_request(attacker, &[(attacker, 1), (attacker, amount)])?;
+    let (attacker_message, attacker_mac) =
+        attacker_request.split_at(attacker_request.len() - block_size);
+
+    let mut forged_request =
+        forger::forge_cbc_mac_extension(victim_message, victim_mac, attacker_message, block_size)?;
+    forged_request.extend_from_slice(attacker_mac);
+
+    Ok(forged_request)
+}
+
+// Challenge 50
+pub fn cbc_mac_hash(message: &[u8], key: &str, iv: &[u8]) -> Result<String> {
+    let mac = aes::cbc_mac(message, key.as_bytes(), iv)?;
+
+    Ok(hex::encode(mac))
+}
+
+pub fn forge_cbc_mac_hash_collision(
+    snippet: &str,
+    target_hash: &str,
+    key: &str,
+    iv: &[u8],
+) -> Result<Vec<u8>> {
+    let target_mac = hex::decode(target_hash).chain_err(|| "could not decode hex string")?;
+    let block_size = iv.len();
+
+    // Comment out the rest of the line, then pad out to a block boundary with spaces. Add
+    // another block of spaces each time the bridge block would break out of the comment.
+    let prefix = format!("{}//", snippet);
+    let aligned_len = aes::padded_len(prefix.len(), block_size);
+
+    for extra_blocks in 0..16 {
+        let mut padded_prefix = prefix.clone();
+        padded_prefix.push_str(&" ".repeat(aligned_len - prefix.len() + extra_blocks * block_size));
+
+        let forged_message = forger::forge_cbc_mac_hash_collision(
+            padded_prefix.as_bytes(),
+            &target_mac,
+            key.as_bytes(),
+            iv,
+        )?;
+
+        if !forged_message[padded_prefix.len()..]
+            .iter()
+            .any(|&byte| byte == b'\n' || byte == b'\r')
+        {
+            return Ok(forged_message);
+        }
+    }
+
+    bail!("could not find a bridge block without a line break")
+}
+
+// Challenge 51
+pub fn create_compression_oracle(session_id: &str, mode: Mode) -> CompressionOracle {
+    CompressionOracle::new(session_id, mode)
+}
+
+pub fn recover_compressed_session_id(oracle: &mut CompressionOracle) -> Result<String> {
+    let alphabet = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/=";
+    let max_padding = 2 * oracle.block_size;
+
+    let session_id = decryptor::break_compression_oracle_fn(
+        b"sessionid=",
+        alphabet,
+        b'\n',
+        max_padding,
+        &mut |plaintext| oracle.compressed_encrypted_len(plaintext),
+    )?;
+
+    String::from_utf8(session_id).chain_err(|| "could not convert vec to utf8 string")
+}
+
+// Challenge 52
+pub fn create_toy_hash(output_bits: usize) -> Result<MdHash<AesCompression>> {
+    MdBuilder::new(AesCompression::new(output_bits)?).build()
+}
+
+pub fn toy_hash(output_bits: usize, message: &[u8]) -> Result<String> {
+    Ok(hex::encode(create_toy_hash(output_bits)?.hash(message)?))
+}
+
+pub fn cascaded_toy_hash(f_bits: usize, g_bits: usize, message: &[u8]) -> Result<String> {
+    Ok(toy_hash(f_bits, message)? + &toy_hash(g_bits, message)?)
+}
+
+pub fn generate_toy_multicollision(
+    output_bits: usize,
+    block_count: usize,
+) -> Result<(Vec<Vec<u8>>, usize)> {
+    let hash = create_toy_hash(output_bits)?;
+    let multicollision = forger::forge_multicollision(&hash, &hash.initial_state, block_count)?;
+
+    Ok((multicollision.messages(), multicollision.compression_calls))
+}
+
+pub fn find_cascaded_toy_hash_collision(
+    f_bits: usize,
+    g_bits: usize,
+) -> Result<(Vec<u8>, Vec<u8>, usize)> {
+    let f = create_toy_hash(f_bits)?;
+    let g = create_toy_hash(g_bits)?;
+    let collision = forger::forge_cascaded_collision(&f, &g)?;
+    let (first, second) = collision.messages;
+
+    Ok((first, second, collision.compression_calls))
+}
+
+// Challenge 53
+pub fn forge_toy_hash_second_preimage(
+    output_bits: usize,
+    message: &[u8],
+    k: usize,
+) -> Result<(Vec<u8>, usize)> {
+    let hash = create_toy_hash(output_bits)?;
+    let second_preimage = forger::forge_second_preimage(&hash, message, k)?;
+
+    Ok((second_preimage.message, second_preimage.compression_calls))
+}
+
+// Challenge 54
+pub fn create_diamond_structure(output_bits: usize, k: usize) -> Result<DiamondStructure> {
+    forger::forge_diamond_structure(&create_toy_hash(output_bits)?, k)
+}
+
+pub fn save_diamond_structure(diamond_structure: &DiamondStructure, path: &str) -> Result<()> {
+    diamond_structure.save(path)
+}
+
+pub fn load_diamond_structure(path: &str) -> Result<DiamondStructure> {
+    DiamondStructure::load(path)
+}
+
+pub fn nostradamus_prediction(
+    output_bits: usize,
+    diamond_structure: &DiamondStructure,
+    prefix_len: usize,
+) -> Result<String> {
+    let hash = create_toy_hash(output_bits)?;
+    let prefix_len = aes::padded_len(prefix_len, hash.block_size());
+
+    Ok(hex::encode(
+        diamond_structure.prediction(&hash, prefix_len)?,
+    ))
+}
+
+pub fn herd_prediction(
+    output_bits: usize,
+    diamond_structure: &DiamondStructure,
+    prefix: &str,
+    prefix_len: usize,
+) -> Result<Vec<u8>> {
+    let hash = create_toy_hash(output_bits)?;
+
+    if prefix.len() > prefix_len {
+        bail!("prefix is longer than the predicted length")
+    }
+
+    // Pad the prefix out with spaces to the whole number of blocks the prediction assumed
+    let mut prefix = prefix.as_bytes().to_vec();
+    prefix.resize(aes::padded_len(prefix_len, hash.block_size()), b' ');
+
+    forger::forge_herded_message(&hash, diamond_structure, &prefix)
+}
+
+// Challenge 55
+pub fn md4_hash(message: &[u8]) -> String {
+    hex::encode(md4::md4(message))
+}
+
+pub fn find_md4_collision() -> Result<(Vec<u8>, Vec<u8>, usize)> {
+    let collision = forger::forge_md4_collision()?;
+    let (first, second) = collision.messages;
+
+    Ok((first, second, collision.attempts))
+}
+
+// Challenge 56
+pub fn create_rc4_cookie_oracle(cookie_base64: &str) -> Result<Rc4CookieOracle> {
+    let cookie = base64::decode(cookie_base64).chain_err(|| "could not decode base64 string")?;
+
+    Ok(Rc4CookieOracle::new(&cookie))
+}
+
+pub fn recover_rc4_cookie(
+    oracle: &Rc4CookieOracle,
+    samples: usize,
+    threads: usize,
+) -> Result<String> {
+    let cookie =
+        decryptor::break_rc4_bias_fn(samples, threads, &|prefix: &[u8]| oracle.encrypt(prefix))?;
+
+    String::from_utf8(cookie).chain_err(|| "could not convert vec to utf8 string")
+}
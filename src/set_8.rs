@@ -0,0 +1,343 @@
+use hex;
+use num_bigint::BigUint;
+use num_traits::One;
+
+use aes::GcmMessage;
+use decryptor;
+use dh::DhParameters;
+use dlog::{self, KangarooParameters, MultiplicativeGroup};
+use dsa::Signature;
+use ec::ecdh::{EcdhParameters, LadderEcdhParameters};
+use ec::ecdsa::{self, Ecdsa, EcdsaParameters};
+use ec::weierstrass::Point;
+use forger;
+use gf128;
+use oracle::{BiasedNonceEcdsaOracle, DhMacOracle, EcdhLadderOracle, EcdhMacOracle, GcmOracle};
+use rsa::{self, HashAlgorithm, Rsa};
+use utility::error::{Result, ResultExt};
+
+// Challenge 57
+pub fn create_dh_mac_oracle(g: &str, p: &str, q: &str) -> Result<DhMacOracle> {
+    Ok(DhMacOracle::new(DhParameters::from_decimal(g, p, q)?))
+}
+
+pub fn dh_public_key(parameters: &DhParameters, x: &str) -> Result<String> {
+    let x = BigUint::parse_bytes(x.as_bytes(), 10).ok_or("could not parse private key")?;
+
+    Ok(parameters.g.modpow(&x, &parameters.p).to_str_radix(10))
+}
+
+pub fn recover_dh_private_key_from_small_subgroups(
+    oracle: &DhMacOracle,
+    factor_bound: u64,
+) -> Result<String> {
+    let parameters = &oracle.dh.parameters;
+    let (x, modulus) = decryptor::break_dh_small_subgroup_fn(parameters, factor_bound, &mut |h| {
+        oracle.respond(h)
+    })?;
+
+    if modulus <= parameters.q {
+        bail!("small subgroups do not cover the order of g")
+    }
+
+    Ok(x.to_str_radix(10))
+}
+
+// Challenge 58
+pub fn kangaroo_discrete_log(
+    parameters: &DhParameters,
+    y: &str,
+    upper: u64,
+    kangaroo_parameters: Option<KangarooParameters>,
+) -> Result<String> {
+    let y = BigUint::parse_bytes(y.as_bytes(), 10).ok_or("could not parse public key")?;
+    let upper = BigUint::from(upper);
+    let kangaroo_parameters =
+        kangaroo_parameters.unwrap_or_else(|| KangarooParameters::for_interval(&upper));
+
+    let x = dlog::kangaroo_with_retries(
+        &MultiplicativeGroup::new(&parameters.p),
+        &parameters.g,
+        &y,
+        &BigUint::from(0u32),
+        &upper,
+        &kangaroo_parameters,
+    )?;
+
+    match x {
+        Some(x) => Ok(x.to_str_radix(10)),
+        None => bail!("wild kangaroo never landed in the trap"),
+    }
+}
+
+pub fn recover_dh_private_key_with_kangaroo(
+    oracle: &DhMacOracle,
+    factor_bound: u64,
+    kangaroo_parameters: Option<KangarooParameters>,
+) -> Result<String> {
+    let x = decryptor::break_dh_subgroup_kangaroo_fn(
+        &oracle.dh.parameters,
+        &oracle.public_key(),
+        factor_bound,
+        kangaroo_parameters,
+        &mut |h| oracle.respond(h),
+    )?;
+
+    Ok(x.to_str_radix(10))
+}
+
+// Challenge 59
+pub fn create_ecdh_mac_oracle(
+    parameters: &[&str; 6],
+    validate_public_keys: bool,
+) -> Result<EcdhMacOracle> {
+    let [a, b, p, gx, gy, n] = *parameters;
+
+    Ok(EcdhMacOracle::new(
+        EcdhParameters::from_decimal(a, b, p, gx, gy, n)?,
+        validate_public_keys,
+    ))
+}
+
+pub fn ecdh_public_key(parameters: &EcdhParameters, x: &str) -> Result<Point> {
+    let x = BigUint::parse_bytes(x.as_bytes(), 10).ok_or("could not parse private key")?;
+
+    Ok(parameters.curve.scale(&parameters.g, &x))
+}
+
+pub fn recover_ecdh_private_key_from_invalid_curves(
+    oracle: &EcdhMacOracle,
+    invalid_curves: &[(&str, &str)],
+    factor_bound: u64,
+) -> Result<String> {
+    let mut curves = Vec::new();
+
+    for &(b, order) in invalid_curves {
+        curves.push((
+            BigUint::parse_bytes(b.as_bytes(), 10).ok_or("could not parse b")?,
+            BigUint::parse_bytes(order.as_bytes(), 10).ok_or("could not parse curve order")?,
+        ));
+    }
+
+    let parameters = &oracle.ecdh.parameters;
+    let (x, modulus) =
+        decryptor::break_ecdh_invalid_curve_fn(parameters, &curves, factor_bound, &mut |h| {
+            oracle.respond(h)
+        })?;
+
+    if modulus <= parameters.n {
+        bail!("invalid curves do not cover the order of g")
+    }
+
+    Ok(x.to_str_radix(10))
+}
+
+// Challenge 60
+pub fn create_ecdh_ladder_oracle(
+    parameters: &[&str; 5],
+    private_key_bits: Option<usize>,
+) -> Result<EcdhLadderOracle> {
+    let [a, b, p, u, n] = *parameters;
+    let parameters = LadderEcdhParameters::from_decimal(a, b, p, u, n)?;
+
+    Ok(match private_key_bits {
+        Some(bits) => {
+            EcdhLadderOracle::new_with_private_key_bound(parameters, &(BigUint::one() << bits))
+        }
+        None => EcdhLadderOracle::new(parameters),
+    })
+}
+
+pub fn recover_ecdh_private_key_from_twist(
+    oracle: &EcdhLadderOracle,
+    curve_order: &str,
+    factor_bound: u64,
+    private_key_bits: Option<usize>,
+    kangaroo_parameters: Option<KangarooParameters>,
+) -> Result<String> {
+    let parameters = &oracle.ecdh.parameters;
+    let curve_order =
+        BigUint::parse_bytes(curve_order.as_bytes(), 10).ok_or("could not parse curve order")?;
+
+    // A curve and its twist between them hold every u, twice over apart from the 2-torsion
+    let twist_order = (&parameters.curve.p << 1) + 2u32 - curve_order;
+    let private_key_bound = match private_key_bits {
+        Some(bits) => BigUint::one() << bits,
+        None => parameters.n.clone(),
+    };
+
+    let x = decryptor::break_ecdh_twist_fn(
+        parameters,
+        &oracle.public_key(),
+        &twist_order,
+        &private_key_bound,
+        factor_bound,
+        kangaroo_parameters,
+        &mut |u| oracle.respond(u),
+    )?;
+
+    Ok(x.to_str_radix(10))
+}
+
+// Challenge 61
+pub fn ecdsa_generate_key_pair(parameters: &[&str; 6]) -> Result<Ecdsa> {
+    let [a, b, p, gx, gy, n] = *parameters;
+
+    Ok(Ecdsa::new(EcdsaParameters::from_decimal(
+        a, b, p, gx, gy, n,
+    )?))
+}
+
+pub fn ecdsa_sign_message(ecdsa: &Ecdsa, message: &str) -> Signature {
+    ecdsa.sign(message.as_bytes())
+}
+
+pub fn ecdsa_verify_signature(
+    public_key: &ecdsa::PublicKey,
+    message: &str,
+    signature: &Signature,
+) -> bool {
+    public_key.verify(message.as_bytes(), signature)
+}
+
+pub fn forge_ecdsa_key_for_signature(
+    public_key: &ecdsa::PublicKey,
+    message: &str,
+    signature: &Signature,
+) -> Result<Ecdsa> {
+    forger::forge_ecdsa_key_for_signature(public_key, message.as_bytes(), signature)
+}
+
+pub fn forge_rsa_key_for_signature(
+    public_key: &rsa::PublicKey,
+    message: &str,
+    signature: &[u8],
+    algorithm: HashAlgorithm,
+) -> Result<Rsa> {
+    forger::forge_rsa_key_for_signature(public_key, message.as_bytes(), signature, algorithm)
+}
+
+// Challenge 62
+pub fn create_biased_nonce_ecdsa_oracle(
+    parameters: &[&str; 6],
+    zero_bits: usize,
+) -> Result<BiasedNonceEcdsaOracle> {
+    let [a, b, p, gx, gy, n] = *parameters;
+
+    Ok(BiasedNonceEcdsaOracle::new(
+        EcdsaParameters::from_decimal(a, b, p, gx, gy, n)?,
+        zero_bits,
+    ))
+}
+
+pub fn recover_ecdsa_private_key_from_biased_nonces(
+    oracle: &BiasedNonceEcdsaOracle,
+    signature_count: usize,
+) -> Result<String> {
+    let signed_messages: Vec<(Vec<u8>, Signature)> = (0..signature_count)
+        .map(|index| {
+            let message = format!("biased nonce message {}", index).into_bytes();
+            let signature = oracle.sign(&message);
+
+            (message, signature)
+        })
+        .collect();
+
+    let d = decryptor::break_ecdsa_biased_nonce(
+        &oracle.public_key(),
+        &signed_messages,
+        oracle.zero_bits,
+    )?;
+
+    Ok(d.to_str_radix(10))
+}
+
+// Challenge 63
+pub fn create_gcm_oracle() -> GcmOracle {
+    GcmOracle::new()
+}
+
+pub fn recover_gcm_authentication_key(
+    oracle: &GcmOracle,
+    aad: &str,
+    plaintexts: &[&str],
+) -> Result<String> {
+    let messages = encrypt_gcm_messages(oracle, aad, plaintexts)?;
+
+    Ok(hex::encode(
+        decryptor::break_gcm_nonce_reuse(&messages)?.to_bytes(),
+    ))
+}
+
+// CTR mode lets the first ciphertext be rewritten to any plaintext of the same length,
+// and the recovered key then supplies a tag the oracle accepts
+pub fn forge_gcm_message_from_nonce_reuse(
+    oracle: &GcmOracle,
+    aad: &str,
+    plaintexts: &[&str],
+    forged_aad: &str,
+    forged_plaintext: &str,
+) -> Result<String> {
+    let messages = encrypt_gcm_messages(oracle, aad, plaintexts)?;
+    let h = decryptor::break_gcm_nonce_reuse(&messages)?;
+
+    if plaintexts[0].len() != forged_plaintext.len() {
+        bail!("the forged plaintext must match the length of the first plaintext")
+    }
+
+    let ciphertext: Vec<u8> = messages[0]
+        .ciphertext
+        .iter()
+        .zip(plaintexts[0].bytes().zip(forged_plaintext.bytes()))
+        .map(|(c, (p, f))| c ^ p ^ f)
+        .collect();
+    let forged = forger::forge_gcm_message(h, &messages[0], forged_aad.as_bytes(), &ciphertext)?;
+
+    String::from_utf8(oracle.decrypt(&forged)?).chain_err(|| "invalid UTF-8 in plaintext")
+}
+
+fn encrypt_gcm_messages(
+    oracle: &GcmOracle,
+    aad: &str,
+    plaintexts: &[&str],
+) -> Result<Vec<GcmMessage>> {
+    plaintexts
+        .iter()
+        .map(|plaintext| oracle.encrypt(plaintext.as_bytes(), aad.as_bytes()))
+        .collect()
+}
+
+// Challenge 64
+pub fn create_truncated_gcm_oracle(tag_len: usize) -> GcmOracle {
+    GcmOracle::new_with_tag_len(tag_len)
+}
+
+pub fn recover_gcm_key_from_truncated_tags(
+    oracle: &GcmOracle,
+    block_exponent: usize,
+) -> Result<String> {
+    // Any plaintext will do, so long as it fills 2^n - 1 blocks
+    let plaintext = vec![0; ((1 << block_exponent) - 1) * gf128::BLOCK_SIZE];
+    let message = oracle.encrypt(&plaintext, &[])?;
+
+    let h = decryptor::break_gcm_truncated_mac_fn(&message, &mut |forged| {
+        oracle.decrypt(forged).is_ok()
+    })?;
+
+    Ok(hex::encode(h.to_bytes()))
+}
+
+// Challenge 65
+pub fn recover_gcm_key_from_length_extension(
+    oracle: &GcmOracle,
+    block_exponent: usize,
+) -> Result<String> {
+    let message = oracle.encrypt(&[], &[])?;
+
+    let h =
+        decryptor::break_gcm_truncated_mac_extension_fn(&message, block_exponent, &mut |forged| {
+            oracle.decrypt(forged).is_ok()
+        })?;
+
+    Ok(hex::encode(h.to_bytes()))
+}
@@ -1,7 +1,11 @@
+use num_bigint::BigUint;
+use num_traits::Num;
+
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{BufRead, BufReader, Read, Write};
 
 use super::error::{Result, ResultExt};
+use dsa::{Signature, SignedMessage};
 
 pub fn buffered_file_reader(path: &str) -> Result<BufReader<File>> {
     let file = File::open(path).chain_err(|| "unable to open file")?;
@@ -25,3 +29,58 @@ pub fn dump_bytes(path: &str) -> Result<Vec<u8>> {
         false => bail!("mismatched sizes: {} vs {}"),
     }
 }
+
+pub fn write_bytes(path: &str, bytes: &[u8]) -> Result<()> {
+    let mut file = File::create(path).chain_err(|| "unable to create file")?;
+    file.write_all(bytes)
+        .chain_err(|| "could not write to file")
+}
+
+// Records of four lines each, "msg: ", "s: ", "r: " and "m: ", with s and r in decimal
+// and m the hex SHA-1 of the message
+pub fn parse_signed_messages<T>(lines: T) -> Result<Vec<SignedMessage>>
+where
+    T: BufRead,
+{
+    let lines = lines
+        .lines()
+        .collect::<::std::io::Result<Vec<String>>>()
+        .chain_err(|| "could not read lines")?;
+
+    if lines.len() % 4 != 0 {
+        bail!("signed messages must come in groups of four lines")
+    }
+
+    lines
+        .chunks(4)
+        .map(|chunk| {
+            let message = signed_message_field(&chunk[0], "msg")?;
+            let s = signed_message_field(&chunk[1], "s")?;
+            let r = signed_message_field(&chunk[2], "r")?;
+            let m = signed_message_field(&chunk[3], "m")?;
+
+            Ok(SignedMessage {
+                hash: BigUint::from_str_radix(m, 16).chain_err(|| "could not parse m")?,
+                message: String::from(message),
+                signature: Signature {
+                    r: BigUint::from_str_radix(r, 10).chain_err(|| "could not parse r")?,
+                    s: BigUint::from_str_radix(s, 10).chain_err(|| "could not parse s")?,
+                },
+            })
+        })
+        .collect()
+}
+
+pub fn read_signed_messages(path: &str) -> Result<Vec<SignedMessage>> {
+    parse_signed_messages(buffered_file_reader(path)?)
+}
+
+fn signed_message_field<'a>(line: &'a str, name: &str) -> Result<&'a str> {
+    match line
+        .strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix(": "))
+    {
+        Some(value) => Ok(value),
+        None => bail!("expected {} field", name),
+    }
+}
@@ -2,4 +2,5 @@ pub mod english;
 pub mod error;
 pub mod file;
 pub mod hamming;
+pub mod number;
 pub mod xor;
@@ -0,0 +1,219 @@
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use openssl::bn::{BigNum, BigNumContext};
+use rand::{self, Rng};
+
+use super::error::{Result, ResultExt};
+
+pub fn invmod(a: &BigUint, modulus: &BigUint) -> Result<BigUint> {
+    let modulus = BigInt::from_biguint(Sign::Plus, modulus.clone());
+    let (mut old_r, mut r) = (BigInt::from_biguint(Sign::Plus, a.clone()), modulus.clone());
+    let (mut old_s, mut s) = (BigInt::one(), BigInt::zero());
+
+    while !r.is_zero() {
+        let quotient = &old_r / &r;
+        let next_r = &old_r - &quotient * &r;
+        old_r = r;
+        r = next_r;
+        let next_s = &old_s - &quotient * &s;
+        old_s = s;
+        s = next_s;
+    }
+
+    if !old_r.is_one() {
+        bail!("value has no inverse for the given modulus")
+    }
+
+    match old_s.mod_floor(&modulus).to_biguint() {
+        Some(inverse) => Ok(inverse),
+        None => bail!("could not convert inverse to unsigned"),
+    }
+}
+
+pub fn generate_prime(bits: usize) -> Result<BigUint> {
+    let mut prime = BigNum::new().chain_err(|| "could not allocate bignum")?;
+    prime
+        .generate_prime(bits as i32, false, None, None)
+        .chain_err(|| "could not generate prime")?;
+
+    Ok(BigUint::from_bytes_be(&prime.to_vec()))
+}
+
+pub fn is_prime(n: &BigUint) -> Result<bool> {
+    let n = BigNum::from_slice(&n.to_bytes_be()).chain_err(|| "could not convert to bignum")?;
+    let mut context = BigNumContext::new().chain_err(|| "could not allocate bignum context")?;
+
+    n.is_prime(64, &mut context)
+        .chain_err(|| "could not test primality")
+}
+
+// A prime p of the given size with p - 1 = 2 * (distinct primes of at most factor_bits
+// bits), none of which appear in avoid. Returns p along with the prime factors of p - 1
+pub fn generate_smooth_prime(
+    bits: usize,
+    factor_bits: usize,
+    avoid: &[BigUint],
+) -> Result<(BigUint, Vec<BigUint>)> {
+    if !(3..=32).contains(&factor_bits) || bits <= factor_bits + 1 {
+        bail!("invalid sizes for smooth prime")
+    }
+
+    let mut rng = rand::thread_rng();
+
+    loop {
+        let mut factors = vec![BigUint::from(2u32)];
+        let mut product = BigUint::from(2u32);
+
+        while bits - product.bits() > factor_bits {
+            let factor = BigUint::from(random_small_prime(&mut rng, factor_bits));
+
+            if !factors.contains(&factor) && !avoid.contains(&factor) {
+                product *= &factor;
+                factors.push(factor);
+            }
+        }
+
+        // The last factor is sized to land p on exactly the right bit length
+        let last_bits = bits - product.bits();
+
+        if last_bits < 3 {
+            continue;
+        }
+
+        for _ in 0..64 {
+            let factor = BigUint::from(random_small_prime(&mut rng, last_bits));
+            let p = &product * &factor + 1u32;
+
+            if p.bits() == bits
+                && !factors.contains(&factor)
+                && !avoid.contains(&factor)
+                && is_prime(&p)?
+            {
+                factors.push(factor);
+                return Ok((p, factors));
+            }
+        }
+    }
+}
+
+// Trial division is plenty for factors this small
+fn random_small_prime<R: Rng>(rng: &mut R, bits: usize) -> u64 {
+    loop {
+        let candidate = rng.gen_range(1u64 << (bits - 1), 1u64 << bits) | 1;
+
+        if (3..)
+            .step_by(2)
+            .take_while(|divisor| divisor * divisor <= candidate)
+            .all(|divisor| !candidate.is_multiple_of(divisor))
+        {
+            return candidate;
+        }
+    }
+}
+
+pub fn to_padded_bytes(value: &BigUint, len: usize) -> Vec<u8> {
+    let bytes = value.to_bytes_be();
+    let mut padded_bytes = vec![0; len.saturating_sub(bytes.len())];
+    padded_bytes.extend_from_slice(&bytes);
+
+    padded_bytes
+}
+
+// Distinct prime factors of n below the bound, found by trial division
+pub fn small_factors(n: &BigUint, bound: u64) -> Vec<BigUint> {
+    let mut remaining = n.clone();
+    let mut factors = Vec::new();
+
+    for candidate in 2..bound {
+        let candidate = BigUint::from(candidate);
+
+        if (&remaining % &candidate).is_zero() {
+            while (&remaining % &candidate).is_zero() {
+                remaining /= &candidate;
+            }
+
+            factors.push(candidate);
+        }
+
+        if remaining.is_one() {
+            break;
+        }
+    }
+
+    factors
+}
+
+// Combine residues with pairwise coprime moduli into (residue, product of moduli)
+pub fn crt(residues: &[(BigUint, BigUint)]) -> Result<(BigUint, BigUint)> {
+    let modulus: BigUint = residues.iter().map(|(_, modulus)| modulus).product();
+    let mut result = BigUint::zero();
+
+    for (residue, residue_modulus) in residues {
+        let partial_modulus = &modulus / residue_modulus;
+        let inverse = invmod(&(&partial_modulus % residue_modulus), residue_modulus)?;
+
+        result = (result + residue * &partial_modulus * inverse) % &modulus;
+    }
+
+    Ok((result, modulus))
+}
+
+// Euler's criterion, counting zero as a square
+pub fn is_quadratic_residue(value: &BigUint, p: &BigUint) -> bool {
+    let value = value % p;
+
+    value.is_zero() || value.modpow(&((p - 1u32) >> 1), p).is_one()
+}
+
+// Tonelli-Shanks square root mod an odd prime p
+pub fn sqrt_mod(value: &BigUint, p: &BigUint) -> Option<BigUint> {
+    let value = value % p;
+
+    if value.is_zero() {
+        return Some(value);
+    }
+
+    if !is_quadratic_residue(&value, p) {
+        return None;
+    }
+
+    let one = BigUint::one();
+    let p_minus_one = p - 1u32;
+    let mut odd_part = p_minus_one.clone();
+    let mut two_power = 0;
+
+    while odd_part.is_even() {
+        odd_part >>= 1;
+        two_power += 1;
+    }
+
+    let mut non_residue = BigUint::from(2u32);
+
+    while is_quadratic_residue(&non_residue, p) {
+        non_residue += 1u32;
+    }
+
+    let mut m = two_power;
+    let mut c = non_residue.modpow(&odd_part, p);
+    let mut t = value.modpow(&odd_part, p);
+    let mut root = value.modpow(&((&odd_part + 1u32) >> 1), p);
+
+    while !t.is_one() {
+        let mut i = 0;
+        let mut t_power = t.clone();
+
+        while !t_power.is_one() {
+            t_power = (&t_power * &t_power) % p;
+            i += 1;
+        }
+
+        let b = c.modpow(&(&one << (m - i - 1)), p);
+        m = i;
+        c = (&b * &b) % p;
+        t = (t * &c) % p;
+        root = (root * b) % p;
+    }
+
+    Some(root)
+}
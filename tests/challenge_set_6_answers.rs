@@ -0,0 +1,56 @@
+pub mod challenge_41 {
+    pub static KEY_BITS: usize = 1024;
+    pub static PLAINTEXT: &'static str = "{time: 1356304276, social: '555-55-5555'}";
+}
+
+pub mod challenge_42 {
+    pub static KEY_BITS: usize = 1024;
+    pub static LARGE_KEY_BITS: usize = 2048;
+    pub static MESSAGE: &'static str = "hi mom";
+}
+
+pub mod challenge_43 {
+    pub static MESSAGE: &'static str = "For those that envy a MC it can be hazardous to your health\n\
+                                        So be friendly, a matter of life and death, just like a etch-a-sketch\n";
+    pub static Y: &'static str = "84ad4719d044495496a3201c8ff484feb45b962e7302e56a392aee4\
+                                  abab3e4bdebf2955b4736012f21a08084056b19bcd7fee56048e004\
+                                  e44984e2f411788efdc837a0d2e5abb7b555039fd243ac01f0fb2ed\
+                                  1dec568280ce678e931868d23eb095fde9d3779191b8c0299d6e07b\
+                                  bb283e6633451e535c45513b2d33c99ea17";
+    pub static R: &'static str = "548099063082341131477253921760299949438196259240";
+    pub static S: &'static str = "857042759984254168557880549501802188789837994940";
+    pub static MAX_NONCE: u64 = 1 << 16;
+    pub static FINGERPRINT: &'static str = "0954edd5e0afe5542a4adf012611a91912a3ec16";
+}
+
+pub mod challenge_44 {
+    pub static FILE_PATH: &'static str = "fixtures/44.txt";
+    pub static Y: &'static str = "2d026f4bf30195ede3a088da85e398ef869611d0f68f0713d51c9c1\
+                                  a3a26c95105d915e2d8cdf26d056b86b8a7b85519b1c23cc3ecdc60\
+                                  62650462e3063bd179c2a6581519f674a61f1d89a1fff27171ebc1b\
+                                  93d4dc57bceb7ae2430f98a6a4d83d8279ee65d71c1203d2c96d65e\
+                                  bbf7cce9d32971c3de5084cce04a2e147821";
+    pub static COLLIDING_PAIRS: [(usize, usize); 3] = [(0, 8), (1, 9), (2, 10)];
+    pub static FINGERPRINT: &'static str = "ca8f6f7c66fa362d40760d135b763eb8527d3d52";
+}
+
+pub mod challenge_45 {
+    pub static MESSAGES: [&str; 2] = ["Hello, world", "Goodbye, world"];
+}
+
+pub mod challenge_46 {
+    pub static KEY_BITS: usize = 1024;
+    pub static BASE64_PLAINTEXT: &'static str = "VGhhdCdzIHdoeSBJIGZvdW5kIHlvdSBkb24ndCBwbGF5IGFyb3VuZCB3aXRoIHRoZSBGdW5reSBDb2xkIE1lZGluYQ==";
+    pub static PLAINTEXT: &'static str =
+        "That's why I found you don't play around with the Funky Cold Medina";
+}
+
+pub mod challenge_47 {
+    pub static KEY_BITS: usize = 256;
+    pub static PLAINTEXT: &'static str = "kick it, CC";
+}
+
+pub mod challenge_48 {
+    pub static KEY_BITS: usize = 768;
+    pub static PLAINTEXT: &'static str = "kick it, CC";
+}
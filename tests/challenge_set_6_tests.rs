@@ -0,0 +1,294 @@
+extern crate matasano;
+extern crate num_bigint;
+
+use matasano::rsa::HashAlgorithm;
+use matasano::set_6;
+use num_bigint::BigUint;
+
+mod challenge_set_6_answers;
+
+#[test]
+fn unpadded_rsa_oracle_rejects_replay() {
+    use challenge_set_6_answers::challenge_41::{KEY_BITS, PLAINTEXT};
+
+    let mut oracle =
+        set_6::create_unpadded_rsa_oracle(KEY_BITS).expect("could not create rsa oracle");
+    let ciphertext = set_6::encrypt_with_unpadded_rsa_oracle(&oracle, PLAINTEXT);
+
+    let plaintext = set_6::decrypt_with_unpadded_rsa_oracle(&mut oracle, &ciphertext)
+        .expect("could not decrypt ciphertext");
+
+    assert_eq!(PLAINTEXT, plaintext);
+
+    if let Err(result) = set_6::decrypt_with_unpadded_rsa_oracle(&mut oracle, &ciphertext) {
+        assert_eq!(
+            "ciphertext has already been decrypted",
+            result.description()
+        );
+    } else {
+        panic!("result was not an error");
+    }
+
+    // Adding the modulus gives a different number that decrypts to the same plaintext
+    let shifted = (BigUint::from_bytes_be(&ciphertext) + oracle.public_key().n).to_bytes_be();
+
+    assert!(set_6::decrypt_with_unpadded_rsa_oracle(&mut oracle, &shifted).is_err());
+}
+
+#[test]
+fn challenge_41_test() {
+    use challenge_set_6_answers::challenge_41::{KEY_BITS, PLAINTEXT};
+
+    let mut oracle =
+        set_6::create_unpadded_rsa_oracle(KEY_BITS).expect("could not create rsa oracle");
+    let ciphertext = set_6::encrypt_with_unpadded_rsa_oracle(&oracle, PLAINTEXT);
+
+    // The victim's ciphertext has already been through the oracle once
+    let _ = set_6::decrypt_with_unpadded_rsa_oracle(&mut oracle, &ciphertext)
+        .expect("could not decrypt ciphertext");
+
+    let recovered_plaintext = set_6::recover_unpadded_rsa_plaintext(&mut oracle, &ciphertext)
+        .expect("Challenge 41: could not recover plaintext");
+
+    assert_eq!(PLAINTEXT, recovered_plaintext);
+}
+
+#[test]
+fn rsa_sign_and_verify() {
+    use challenge_set_6_answers::challenge_42::{KEY_BITS, MESSAGE};
+
+    let rsa = set_6::generate_rsa_key_pair(KEY_BITS).expect("could not generate key pair");
+    let public_key = rsa.public_key();
+
+    for &algorithm in &[HashAlgorithm::Sha1, HashAlgorithm::Sha256] {
+        let signature =
+            set_6::rsa_sign_message(&rsa, MESSAGE, algorithm).expect("could not sign message");
+
+        assert!(set_6::rsa_verify_signature(
+            &public_key,
+            MESSAGE,
+            &signature,
+            algorithm
+        ));
+        assert!(set_6::rsa_sloppy_verify_signature(
+            &public_key,
+            MESSAGE,
+            &signature
+        ));
+        assert!(!set_6::rsa_verify_signature(
+            &public_key,
+            "hi dad",
+            &signature,
+            algorithm
+        ));
+    }
+}
+
+#[test]
+fn challenge_42_test() {
+    use challenge_set_6_answers::challenge_42::{KEY_BITS, MESSAGE};
+
+    let rsa = set_6::generate_rsa_key_pair(KEY_BITS).expect("could not generate key pair");
+    let public_key = rsa.public_key();
+
+    let forged_signature = set_6::forge_rsa_signature(&public_key, MESSAGE, HashAlgorithm::Sha1)
+        .expect("Challenge 42: could not forge signature");
+
+    assert!(set_6::rsa_sloppy_verify_signature(
+        &public_key,
+        MESSAGE,
+        &forged_signature
+    ));
+    assert!(!set_6::rsa_verify_signature(
+        &public_key,
+        MESSAGE,
+        &forged_signature,
+        HashAlgorithm::Sha1
+    ));
+}
+
+#[test]
+fn forge_sha256_signature() {
+    use challenge_set_6_answers::challenge_42::{LARGE_KEY_BITS, MESSAGE};
+
+    let rsa = set_6::generate_rsa_key_pair(LARGE_KEY_BITS).expect("could not generate key pair");
+    let public_key = rsa.public_key();
+
+    let forged_signature = set_6::forge_rsa_signature(&public_key, MESSAGE, HashAlgorithm::Sha256)
+        .expect("could not forge signature");
+
+    assert!(set_6::rsa_sloppy_verify_signature(
+        &public_key,
+        MESSAGE,
+        &forged_signature
+    ));
+    assert!(!set_6::rsa_verify_signature(
+        &public_key,
+        MESSAGE,
+        &forged_signature,
+        HashAlgorithm::Sha256
+    ));
+}
+
+#[test]
+fn dsa_sign_and_verify() {
+    use challenge_set_6_answers::challenge_43::MESSAGE;
+
+    let dsa = set_6::dsa_generate_key_pair().expect("could not generate key pair");
+    let public_key = dsa.public_key();
+    let signature = set_6::dsa_sign_message(&dsa, MESSAGE);
+
+    assert!(set_6::dsa_verify_signature(
+        &public_key,
+        MESSAGE,
+        &signature
+    ));
+    assert!(!set_6::dsa_verify_signature(
+        &public_key,
+        "not the message",
+        &signature
+    ));
+}
+
+#[test]
+fn challenge_43_test() {
+    use challenge_set_6_answers::challenge_43::{FINGERPRINT, MAX_NONCE, MESSAGE, R, S, Y};
+
+    let fingerprint = set_6::recover_dsa_private_key_from_nonce_range(Y, MESSAGE, R, S, MAX_NONCE)
+        .expect("Challenge 43: could not recover private key");
+
+    assert_eq!(FINGERPRINT, fingerprint);
+}
+
+#[test]
+fn challenge_44_test() {
+    use challenge_set_6_answers::challenge_44::{COLLIDING_PAIRS, FILE_PATH, FINGERPRINT, Y};
+
+    let (fingerprint, colliding_pairs) =
+        set_6::recover_dsa_private_key_from_repeated_nonce(FILE_PATH, Y)
+            .expect("Challenge 44: could not recover private key");
+
+    assert_eq!(FINGERPRINT, fingerprint);
+    assert_eq!(&COLLIDING_PAIRS[..], &colliding_pairs[..]);
+}
+
+#[test]
+fn dsa_strict_verify_rejects_bad_generator() {
+    use challenge_set_6_answers::challenge_45::MESSAGES;
+
+    let dsa = set_6::dsa_generate_key_pair().expect("could not generate key pair");
+    let public_key = dsa.public_key();
+    let signature = set_6::dsa_sign_message(&dsa, MESSAGES[0]);
+    let tampered_public_key = set_6::dsa_public_key_with_p_plus_one_generator(&public_key);
+
+    assert!(set_6::dsa_sloppy_verify_signature(
+        &public_key,
+        MESSAGES[0],
+        &signature
+    ));
+    assert!(!set_6::dsa_verify_signature(
+        &tampered_public_key,
+        MESSAGES[0],
+        &signature
+    ));
+}
+
+#[test]
+fn challenge_45_zero_generator_test() {
+    use challenge_set_6_answers::challenge_45::MESSAGES;
+
+    let dsa = set_6::dsa_generate_key_pair().expect("could not generate key pair");
+    let public_key = set_6::dsa_public_key_with_zero_generator(&dsa.public_key());
+
+    let signature = set_6::forge_dsa_zero_generator_signature(&public_key)
+        .expect("Challenge 45: could not forge signature");
+
+    for message in MESSAGES.iter() {
+        assert!(set_6::dsa_sloppy_verify_signature(
+            &public_key,
+            message,
+            &signature
+        ));
+        assert!(!set_6::dsa_verify_signature(
+            &public_key,
+            message,
+            &signature
+        ));
+    }
+}
+
+#[test]
+fn challenge_45_magic_signature_test() {
+    use challenge_set_6_answers::challenge_45::MESSAGES;
+
+    let dsa = set_6::dsa_generate_key_pair().expect("could not generate key pair");
+    let public_key = set_6::dsa_public_key_with_p_plus_one_generator(&dsa.public_key());
+
+    let signature = set_6::forge_dsa_magic_signature(&public_key)
+        .expect("Challenge 45: could not forge signature");
+
+    for message in MESSAGES.iter() {
+        assert!(set_6::dsa_sloppy_verify_signature(
+            &public_key,
+            message,
+            &signature
+        ));
+        assert!(!set_6::dsa_verify_signature(
+            &public_key,
+            message,
+            &signature
+        ));
+    }
+}
+
+#[test]
+fn challenge_46_test() {
+    use challenge_set_6_answers::challenge_46::{BASE64_PLAINTEXT, KEY_BITS, PLAINTEXT};
+
+    let oracle = set_6::create_rsa_parity_oracle(KEY_BITS).expect("could not create rsa oracle");
+    let ciphertext = set_6::encrypt_base64_with_rsa_parity_oracle(&oracle, BASE64_PLAINTEXT)
+        .expect("could not encrypt plaintext");
+
+    let mut progress = Vec::new();
+    let plaintext =
+        set_6::recover_rsa_parity_plaintext(&oracle, &ciphertext, &mut |upper_bound: &[u8]| {
+            progress.push(upper_bound.to_vec())
+        })
+        .expect("Challenge 46: could not recover plaintext");
+
+    assert_eq!(PLAINTEXT, plaintext);
+    assert_eq!(KEY_BITS, progress.len());
+    assert_eq!(PLAINTEXT.as_bytes(), &progress[KEY_BITS - 1][..]);
+}
+
+#[test]
+fn challenge_47_test() {
+    use challenge_set_6_answers::challenge_47::{KEY_BITS, PLAINTEXT};
+
+    let oracle = set_6::create_rsa_padding_oracle(KEY_BITS).expect("could not create rsa oracle");
+    let ciphertext = set_6::encrypt_with_rsa_padding_oracle(&oracle, PLAINTEXT)
+        .expect("could not encrypt plaintext");
+
+    let (plaintext, _) = set_6::recover_rsa_padded_plaintext(&oracle, &ciphertext)
+        .expect("Challenge 47: could not recover plaintext");
+
+    assert_eq!(PLAINTEXT, plaintext);
+}
+
+// The same attack as challenge 47 against a 768-bit key. It takes tens of thousands of
+// oracle queries, under a minute with --release but up to half an hour or more unoptimized,
+// so only run on request
+#[test]
+#[ignore]
+fn challenge_48_test() {
+    use challenge_set_6_answers::challenge_48::{KEY_BITS, PLAINTEXT};
+
+    let oracle = set_6::create_rsa_padding_oracle(KEY_BITS).expect("could not create rsa oracle");
+    let ciphertext = set_6::encrypt_with_rsa_padding_oracle(&oracle, PLAINTEXT)
+        .expect("could not encrypt plaintext");
+
+    let (plaintext, _) = set_6::recover_rsa_padded_plaintext(&oracle, &ciphertext)
+        .expect("Challenge 48: could not recover plaintext");
+
+    assert_eq!(PLAINTEXT, plaintext);
+}
@@ -0,0 +1,48 @@
+pub mod challenge_49 {
+    pub static BLOCK_SIZE: usize = 16;
+    pub static ATTACKER: u64 = 1;
+    pub static VICTIM: u64 = 2;
+    pub static AMOUNT: u64 = 1000000;
+    pub static VICTIM_TRANSACTIONS: [(u64, u64); 2] = [(3, 50), (4, 125)];
+}
+
+pub mod challenge_50 {
+    pub static KEY: &'static str = "YELLOW SUBMARINE";
+    pub static IV: [u8; 16] = [0; 16];
+    pub static ORIGINAL_SNIPPET: &'static str = "alert('MZA who was that?');\n";
+    pub static ORIGINAL_HASH: &'static str = "296b8d7cb78a243dda4d0a61d33bbdd1";
+    pub static FORGED_SNIPPET: &'static str = "alert('Ayo, the Wu is back!');";
+}
+
+pub mod challenge_51 {
+    pub static SESSION_ID: &'static str = "TmV2ZXIgcmV2ZWFsIHRoZSBXdS1UYW5nIFNlY3JldCE=";
+}
+
+pub mod challenge_52 {
+    pub static F_BITS: usize = 16;
+    pub static G_BITS: usize = 32;
+    pub static MULTICOLLISION_BLOCKS: usize = 4;
+}
+
+pub mod challenge_53 {
+    pub static K: usize = 10;
+    pub static OUTPUT_BITS: usize = 24;
+}
+
+pub mod challenge_54 {
+    pub static K: usize = 6;
+    pub static OUTPUT_BITS: usize = 24;
+    pub static PREFIX_LEN: usize = 160;
+    pub static SCORES: &'static str =
+        "Yankees 4, Red Sox 3\nCubs 7, Cardinals 2\nDodgers 5, Giants 1\n";
+}
+
+pub mod challenge_56 {
+    pub static COOKIE: &'static str = "QkUgU1VSRSBUTyBEUklOSyBZT1VSIE9WQUxUSU5F";
+    pub static PLAINTEXT: &'static str = "BE SURE TO DRINK YOUR OVALTINE";
+    pub static SAMPLES: usize = 1 << 24;
+    pub static SHORT_COOKIE: &'static str = "QkU=";
+    pub static SHORT_PLAINTEXT: &'static str = "BE";
+    pub static SHORT_SAMPLES: usize = 1 << 16;
+    pub static THREADS: usize = 4;
+}
@@ -0,0 +1,329 @@
+extern crate matasano;
+
+use matasano::analyzer::Mode;
+use matasano::set_7;
+
+use std::env;
+use std::fs;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+mod challenge_set_7_answers;
+
+// Unique per run so parallel test runs never share a file
+fn temporary_path(name: &str) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock is before the epoch")
+        .subsec_nanos();
+    let file_name = format!("{}_{}_{}.txt", name, process::id(), nanos);
+
+    env::temp_dir()
+        .join(file_name)
+        .to_str()
+        .expect("invalid temporary path")
+        .to_string()
+}
+
+#[test]
+fn bank_verify_transfer_request() {
+    use challenge_set_7_answers::challenge_49::{AMOUNT, ATTACKER, VICTIM};
+
+    let bank = set_7::create_bank();
+    let mut request = set_7::bank_transfer_request(&bank, VICTIM, ATTACKER, AMOUNT)
+        .expect("could not create request");
+
+    let transfer =
+        set_7::bank_verify_transfer_request(&bank, &request).expect("could not verify request");
+
+    assert_eq!(VICTIM, transfer.from);
+    assert_eq!(ATTACKER, transfer.to);
+    assert_eq!(AMOUNT, transfer.amount);
+
+    request[5] ^= 0x01;
+
+    if let Err(result) = set_7::bank_verify_transfer_request(&bank, &request) {
+        assert_eq!("invalid MAC", result.description());
+    } else {
+        panic!("result was not an error");
+    }
+}
+
+#[test]
+fn challenge_49_iv_test() {
+    use challenge_set_7_answers::challenge_49::{AMOUNT, ATTACKER, BLOCK_SIZE, VICTIM};
+
+    let bank = set_7::create_bank();
+    let request = set_7::bank_transfer_request(&bank, ATTACKER, ATTACKER, AMOUNT)
+        .expect("could not create request");
+
+    let forged_request = set_7::forge_transfer_request(&request, VICTIM, BLOCK_SIZE)
+        .expect("Challenge 49: could not forge request");
+    let transfer = set_7::bank_verify_transfer_request(&bank, &forged_request)
+        .expect("Challenge 49: could not verify forged request");
+
+    assert_eq!(VICTIM, transfer.from);
+    assert_eq!(ATTACKER, transfer.to);
+    assert_eq!(AMOUNT, transfer.amount);
+}
+
+#[test]
+fn challenge_49_length_extension_test() {
+    use challenge_set_7_answers::challenge_49::{AMOUNT, ATTACKER, VICTIM, VICTIM_TRANSACTIONS};
+
+    let bank = set_7::create_bank();
+    let victim_request = set_7::bank_transaction_list_request(&bank, VICTIM, &VICTIM_TRANSACTIONS)
+        .expect("could not create request");
+
+    let forged_request =
+        set_7::forge_transaction_list_request(&bank, &victim_request, ATTACKER, AMOUNT)
+            .expect("Challenge 49: could not forge request");
+    let transaction_list = set_7::bank_verify_transaction_list_request(&bank, &forged_request)
+        .expect("Challenge 49: could not verify forged request");
+
+    assert_eq!(VICTIM, transaction_list.from);
+    assert_eq!(&VICTIM_TRANSACTIONS[0], &transaction_list.transactions[0]);
+    assert_eq!(
+        Some(&(ATTACKER, AMOUNT)),
+        transaction_list.transactions.last()
+    );
+}
+
+#[test]
+fn cbc_mac_hash() {
+    use challenge_set_7_answers::challenge_50::{IV, KEY, ORIGINAL_HASH, ORIGINAL_SNIPPET};
+
+    let hash =
+        set_7::cbc_mac_hash(ORIGINAL_SNIPPET.as_bytes(), KEY, &IV).expect("could not hash snippet");
+
+    assert_eq!(ORIGINAL_HASH, hash);
+}
+
+#[test]
+fn challenge_50_test() {
+    use challenge_set_7_answers::challenge_50::{FORGED_SNIPPET, IV, KEY, ORIGINAL_HASH};
+
+    let forged_message =
+        set_7::forge_cbc_mac_hash_collision(FORGED_SNIPPET, ORIGINAL_HASH, KEY, &IV)
+            .expect("Challenge 50: could not forge snippet");
+    let forged_hash =
+        set_7::cbc_mac_hash(&forged_message, KEY, &IV).expect("could not hash snippet");
+
+    assert!(forged_message.starts_with(FORGED_SNIPPET.as_bytes()));
+    assert_eq!(ORIGINAL_HASH, forged_hash);
+}
+
+#[test]
+fn challenge_51_ctr_test() {
+    use challenge_set_7_answers::challenge_51::SESSION_ID;
+
+    let mut oracle = set_7::create_compression_oracle(SESSION_ID, Mode::Ctr);
+
+    let session_id = set_7::recover_compressed_session_id(&mut oracle)
+        .expect("Challenge 51: could not recover session id");
+
+    assert_eq!(SESSION_ID, session_id);
+}
+
+#[test]
+fn challenge_51_cbc_test() {
+    use challenge_set_7_answers::challenge_51::SESSION_ID;
+
+    let mut oracle = set_7::create_compression_oracle(SESSION_ID, Mode::Cbc);
+
+    let session_id = set_7::recover_compressed_session_id(&mut oracle)
+        .expect("Challenge 51: could not recover session id");
+
+    assert_eq!(SESSION_ID, session_id);
+}
+
+#[test]
+fn challenge_52_multicollision_test() {
+    use challenge_set_7_answers::challenge_52::{F_BITS, MULTICOLLISION_BLOCKS};
+
+    let (messages, compression_calls) =
+        set_7::generate_toy_multicollision(F_BITS, MULTICOLLISION_BLOCKS)
+            .expect("Challenge 52: could not generate multicollision");
+
+    assert_eq!(1 << MULTICOLLISION_BLOCKS, messages.len());
+    assert!(compression_calls > 0);
+
+    let hash = set_7::toy_hash(F_BITS, &messages[0]).expect("Challenge 52: could not hash");
+
+    for (index, message) in messages.iter().enumerate() {
+        assert!(messages[index + 1..].iter().all(|other| other != message));
+        assert_eq!(
+            hash,
+            set_7::toy_hash(F_BITS, message).expect("Challenge 52: could not hash")
+        );
+    }
+}
+
+#[test]
+fn challenge_52_test() {
+    use challenge_set_7_answers::challenge_52::{F_BITS, G_BITS};
+
+    let (first, second, compression_calls) =
+        set_7::find_cascaded_toy_hash_collision(F_BITS, G_BITS)
+            .expect("Challenge 52: could not find cascaded collision");
+
+    assert!(first != second);
+    assert!(compression_calls > 0);
+    assert_eq!(
+        set_7::cascaded_toy_hash(F_BITS, G_BITS, &first).expect("Challenge 52: could not hash"),
+        set_7::cascaded_toy_hash(F_BITS, G_BITS, &second).expect("Challenge 52: could not hash")
+    );
+}
+
+#[test]
+fn challenge_53_test() {
+    use challenge_set_7_answers::challenge_53::{K, OUTPUT_BITS};
+
+    let message: String = (0..1 << K)
+        .map(|index| format!("Block #{:09}", index))
+        .collect();
+
+    let (forged_message, compression_calls) =
+        set_7::forge_toy_hash_second_preimage(OUTPUT_BITS, message.as_bytes(), K)
+            .expect("Challenge 53: could not forge second preimage");
+
+    assert!(compression_calls > 0);
+    assert!(forged_message != message.as_bytes());
+    assert_eq!(message.len(), forged_message.len());
+    assert_eq!(
+        set_7::toy_hash(OUTPUT_BITS, message.as_bytes()).expect("Challenge 53: could not hash"),
+        set_7::toy_hash(OUTPUT_BITS, &forged_message).expect("Challenge 53: could not hash")
+    );
+}
+
+#[test]
+fn challenge_54_test() {
+    use challenge_set_7_answers::challenge_54::{K, OUTPUT_BITS, PREFIX_LEN, SCORES};
+
+    let diamond_structure = set_7::create_diamond_structure(OUTPUT_BITS, K)
+        .expect("Challenge 54: could not create diamond structure");
+    let prediction = set_7::nostradamus_prediction(OUTPUT_BITS, &diamond_structure, PREFIX_LEN)
+        .expect("Challenge 54: could not make prediction");
+
+    let path = temporary_path("challenge_54_diamond_structure");
+
+    set_7::save_diamond_structure(&diamond_structure, &path)
+        .expect("Challenge 54: could not save diamond structure");
+    let diamond_structure = set_7::load_diamond_structure(&path)
+        .expect("Challenge 54: could not load diamond structure");
+    fs::remove_file(&path).expect("Challenge 54: could not remove diamond structure");
+
+    assert_eq!(
+        prediction,
+        set_7::nostradamus_prediction(OUTPUT_BITS, &diamond_structure, PREFIX_LEN)
+            .expect("Challenge 54: could not make prediction")
+    );
+
+    let message = set_7::herd_prediction(OUTPUT_BITS, &diamond_structure, SCORES, PREFIX_LEN)
+        .expect("Challenge 54: could not herd prediction");
+
+    assert!(message.starts_with(SCORES.as_bytes()));
+    assert_eq!(
+        prediction,
+        set_7::toy_hash(OUTPUT_BITS, &message).expect("Challenge 54: could not hash")
+    );
+}
+
+#[test]
+fn challenge_54_rejects_mismatched_diamond_structure() {
+    use challenge_set_7_answers::challenge_54::{OUTPUT_BITS, PREFIX_LEN, SCORES};
+
+    let path = temporary_path("challenge_54_root_only");
+    fs::write(&path, "root 0a0b0c\n").expect("Challenge 54: could not write file");
+    let loaded = set_7::load_diamond_structure(&path);
+    fs::remove_file(&path).expect("Challenge 54: could not remove file");
+
+    assert!(loaded.is_err());
+
+    let diamond_structure = set_7::create_diamond_structure(OUTPUT_BITS, 1)
+        .expect("Challenge 54: could not create diamond structure");
+
+    if let Err(result) =
+        set_7::herd_prediction(OUTPUT_BITS + 8, &diamond_structure, SCORES, PREFIX_LEN)
+    {
+        assert_eq!(
+            "diamond structure does not match the hash",
+            result.description()
+        );
+    } else {
+        panic!("result was not an error");
+    }
+}
+
+#[test]
+fn challenge_54_rejects_tampered_diamond_structure() {
+    use challenge_set_7_answers::challenge_54::{OUTPUT_BITS, PREFIX_LEN, SCORES};
+
+    let diamond_structure = set_7::create_diamond_structure(OUTPUT_BITS, 2)
+        .expect("Challenge 54: could not create diamond structure");
+    let path = temporary_path("challenge_54_tampered");
+
+    set_7::save_diamond_structure(&diamond_structure, &path)
+        .expect("Challenge 54: could not save diamond structure");
+
+    // Flip the last hex digit of the first saved block, keeping every size intact
+    let contents = fs::read_to_string(&path).expect("Challenge 54: could not read file");
+    let mut lines: Vec<String> = contents.lines().map(|line| line.to_string()).collect();
+    let last = lines[0].pop().expect("Challenge 54: empty line");
+    lines[0].push(if last == '0' { '1' } else { '0' });
+    fs::write(&path, lines.join("\n")).expect("Challenge 54: could not write file");
+
+    let tampered = set_7::load_diamond_structure(&path);
+    fs::remove_file(&path).expect("Challenge 54: could not remove file");
+    let tampered = tampered.expect("Challenge 54: could not load diamond structure");
+
+    if let Err(result) = set_7::herd_prediction(OUTPUT_BITS, &tampered, SCORES, PREFIX_LEN) {
+        assert_eq!(
+            "diamond structure does not match the hash",
+            result.description()
+        );
+    } else {
+        panic!("result was not an error");
+    }
+}
+
+#[test]
+fn challenge_55_test() {
+    let (first, second, attempts) =
+        set_7::find_md4_collision().expect("Challenge 55: could not find collision");
+
+    assert!(first != second);
+    assert!(attempts > 0);
+    assert_eq!(set_7::md4_hash(&first), set_7::md4_hash(&second));
+}
+
+#[test]
+fn challenge_56_short_cookie_test() {
+    use challenge_set_7_answers::challenge_56::{
+        SHORT_COOKIE, SHORT_PLAINTEXT, SHORT_SAMPLES, THREADS,
+    };
+
+    // Both bytes line up with Z2, which needs far fewer samples than Z16 and Z32
+    let oracle = set_7::create_rc4_cookie_oracle(SHORT_COOKIE)
+        .expect("Challenge 56: could not create oracle");
+
+    let cookie = set_7::recover_rc4_cookie(&oracle, SHORT_SAMPLES, THREADS)
+        .expect("Challenge 56: could not recover cookie");
+
+    assert_eq!(SHORT_PLAINTEXT, cookie);
+}
+
+// Takes a few minutes per cookie byte pair on a single core, so only run on request
+#[test]
+#[ignore]
+fn challenge_56_test() {
+    use challenge_set_7_answers::challenge_56::{COOKIE, PLAINTEXT, SAMPLES, THREADS};
+
+    let oracle =
+        set_7::create_rc4_cookie_oracle(COOKIE).expect("Challenge 56: could not create oracle");
+
+    let cookie = set_7::recover_rc4_cookie(&oracle, SAMPLES, THREADS)
+        .expect("Challenge 56: could not recover cookie");
+
+    assert_eq!(PLAINTEXT, cookie);
+}
@@ -0,0 +1,107 @@
+pub mod challenge_57 {
+    pub static FACTOR_BOUND: u64 = 1 << 16;
+    pub static G: &'static str = "4565356397095740655436854503483826832136106141639563487732438195\
+                                  3436904376061178283180424182381848962123523291186081000831875350\
+                                  33402010599512641674644143";
+    pub static P: &'static str = "7199773997391911030609999317773941274322764333428698921736339643\
+                                  9283464537000853588029739004855929104754800897261407081024749574\
+                                  29903531369589969318716771";
+    pub static Q: &'static str = "236234353446506858198510045061214171961";
+}
+
+pub mod challenge_58 {
+    pub static FACTOR_BOUND: u64 = 1 << 16;
+    pub static G: &'static str = "6229523353339612969781592660847410858898813587384599399782901799\
+                                  3606363556674025855516778300905856739796346610314008264748661165\
+                                  7350811560630587013183357";
+    pub static P: &'static str = "1147037487492527565811666350723216140208665025845389627453499167\
+                                  6898999262641581519101074740642369848233294239851519212341844337\
+                                  347119899874391456329785623";
+    pub static Q: &'static str = "335062023296420808191071248367701059461";
+    pub static SHORT_UPPER: u64 = 1 << 20;
+    pub static SHORT_Y: &'static str = "776007384803268950539500570567736587665462918929805277575459\
+                                        760744661755860039407676481423608199164309423988677248105225\
+                                        4010323780165093955236429914607119";
+}
+
+pub mod challenge_59 {
+    pub static FACTOR_BOUND: u64 = 1 << 16;
+    pub static INVALID_CURVES: [(&'static str, &'static str); 3] = [
+        ("210", "233970423115425145550826547352470124412"),
+        ("504", "233970423115425145544350131142039591210"),
+        ("727", "233970423115425145545378039958152057148"),
+    ];
+    pub static PARAMETERS: [&'static str; 6] = [
+        "-95051",
+        "11279326",
+        "233970423115425145524320034830162017933",
+        "182",
+        "85518893674295321206118380980485522083",
+        "29246302889428143187362802287225875743",
+    ];
+}
+
+pub mod challenge_60 {
+    pub static CURVE_ORDER: &'static str = "233970423115425145498902418297807005944";
+    pub static FACTOR_BOUND: u64 = 1 << 22;
+    pub static PARAMETERS: [&'static str; 5] = [
+        "534",
+        "1",
+        "233970423115425145524320034830162017933",
+        "4",
+        "29246302889428143187362802287225875743",
+    ];
+    pub static SHORT_FACTOR_BOUND: u64 = 1 << 17;
+    pub static SHORT_PRIVATE_KEY_BITS: usize = 72;
+}
+
+pub mod challenge_61 {
+    pub static MESSAGE: &'static str = "I'll give you a key that fits any lock";
+    pub static PARAMETERS: [&'static str; 6] = [
+        "-95051",
+        "11279326",
+        "233970423115425145524320034830162017933",
+        "182",
+        "85518893674295321206118380980485522083",
+        "29246302889428143187362802287225875743",
+    ];
+    pub static RSA_BITS: usize = 512;
+}
+
+pub mod challenge_62 {
+    pub static PARAMETERS: [&'static str; 6] = [
+        "-95051",
+        "11279326",
+        "233970423115425145524320034830162017933",
+        "182",
+        "85518893674295321206118380980485522083",
+        "29246302889428143187362802287225875743",
+    ];
+    pub static SIGNATURE_COUNT: usize = 20;
+    pub static ZERO_BITS: usize = 8;
+}
+
+pub mod challenge_63 {
+    pub static AAD: &'static str = "from: alice, to: bob";
+    pub static FORGED_AAD: &'static str = "from: alice, to: eve";
+    pub static FORGED_PLAINTEXT: &'static str = "Pay Eve ten thousand dollars, urgently";
+    pub static PLAINTEXTS: [&'static str; 3] = [
+        "Pay Bob one hundred dollars by Tuesday",
+        "Lunch is at noon in the usual place",
+        "Remember to rotate the nonces, please!",
+    ];
+}
+
+pub mod challenge_64 {
+    pub static BLOCK_EXPONENT: usize = 17;
+    pub static SHORT_BLOCK_EXPONENT: usize = 9;
+    pub static SHORT_TAG_LEN: usize = 2;
+    pub static TAG_LEN: usize = 4;
+}
+
+pub mod challenge_65 {
+    pub static BLOCK_EXPONENT: usize = 17;
+    pub static SHORT_BLOCK_EXPONENT: usize = 10;
+    pub static SHORT_TAG_LEN: usize = 2;
+    pub static TAG_LEN: usize = 4;
+}
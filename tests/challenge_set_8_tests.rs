@@ -0,0 +1,306 @@
+extern crate hex;
+extern crate matasano;
+
+use matasano::oracle::GcmOracle;
+use matasano::rsa::HashAlgorithm;
+use matasano::{aes, set_6, set_8};
+
+mod challenge_set_8_answers;
+
+#[test]
+fn challenge_57_test() {
+    use challenge_set_8_answers::challenge_57::{FACTOR_BOUND, G, P, Q};
+
+    let oracle =
+        set_8::create_dh_mac_oracle(G, P, Q).expect("Challenge 57: could not create oracle");
+
+    let x = set_8::recover_dh_private_key_from_small_subgroups(&oracle, FACTOR_BOUND)
+        .expect("Challenge 57: could not recover private key");
+
+    assert_eq!(
+        oracle.public_key().to_str_radix(10),
+        set_8::dh_public_key(&oracle.dh.parameters, &x)
+            .expect("Challenge 57: could not compute public key")
+    );
+}
+
+#[test]
+fn challenge_58_kangaroo_test() {
+    use challenge_set_8_answers::challenge_58::{G, P, Q, SHORT_UPPER, SHORT_Y};
+
+    let oracle =
+        set_8::create_dh_mac_oracle(G, P, Q).expect("Challenge 58: could not create oracle");
+    let parameters = &oracle.dh.parameters;
+
+    let x = set_8::kangaroo_discrete_log(parameters, SHORT_Y, SHORT_UPPER, None)
+        .expect("Challenge 58: could not take discrete log");
+
+    assert_eq!(
+        SHORT_Y,
+        set_8::dh_public_key(parameters, &x).expect("Challenge 58: could not compute public key")
+    );
+}
+
+#[test]
+fn challenge_58_test() {
+    use challenge_set_8_answers::challenge_58::{FACTOR_BOUND, G, P, Q};
+
+    let oracle =
+        set_8::create_dh_mac_oracle(G, P, Q).expect("Challenge 58: could not create oracle");
+
+    let x = set_8::recover_dh_private_key_with_kangaroo(&oracle, FACTOR_BOUND, None)
+        .expect("Challenge 58: could not recover private key");
+
+    assert_eq!(
+        oracle.public_key().to_str_radix(10),
+        set_8::dh_public_key(&oracle.dh.parameters, &x)
+            .expect("Challenge 58: could not compute public key")
+    );
+}
+
+#[test]
+fn challenge_59_test() {
+    use challenge_set_8_answers::challenge_59::{FACTOR_BOUND, INVALID_CURVES, PARAMETERS};
+
+    let oracle = set_8::create_ecdh_mac_oracle(&PARAMETERS, false)
+        .expect("Challenge 59: could not create oracle");
+
+    let x =
+        set_8::recover_ecdh_private_key_from_invalid_curves(&oracle, &INVALID_CURVES, FACTOR_BOUND)
+            .expect("Challenge 59: could not recover private key");
+
+    assert_eq!(
+        oracle.public_key(),
+        set_8::ecdh_public_key(&oracle.ecdh.parameters, &x)
+            .expect("Challenge 59: could not compute public key")
+    );
+}
+
+#[test]
+fn challenge_59_validation_test() {
+    use challenge_set_8_answers::challenge_59::{FACTOR_BOUND, INVALID_CURVES, PARAMETERS};
+
+    let oracle = set_8::create_ecdh_mac_oracle(&PARAMETERS, true)
+        .expect("Challenge 59: could not create oracle");
+
+    if let Err(result) =
+        set_8::recover_ecdh_private_key_from_invalid_curves(&oracle, &INVALID_CURVES, FACTOR_BOUND)
+    {
+        assert_eq!("point is not on the curve", result.description());
+    } else {
+        panic!("result was not an error");
+    }
+}
+
+// The odd twist factors below 2^17 give x mod about 2^45, so a 72-bit key still leaves a
+// kangaroo search of about 2^27. Every factor after the first needs its sign settled, and
+// the kangaroo tries both signs of the residue and of Bob's point before one lands
+#[test]
+fn challenge_60_short_test() {
+    use challenge_set_8_answers::challenge_60::{
+        CURVE_ORDER, PARAMETERS, SHORT_FACTOR_BOUND, SHORT_PRIVATE_KEY_BITS,
+    };
+
+    let oracle = set_8::create_ecdh_ladder_oracle(&PARAMETERS, Some(SHORT_PRIVATE_KEY_BITS))
+        .expect("Challenge 60: could not create oracle");
+
+    let x = set_8::recover_ecdh_private_key_from_twist(
+        &oracle,
+        CURVE_ORDER,
+        SHORT_FACTOR_BOUND,
+        Some(SHORT_PRIVATE_KEY_BITS),
+        None,
+    )
+    .expect("Challenge 60: could not recover private key");
+
+    assert_eq!(oracle.ecdh.x.to_str_radix(10), x);
+}
+
+// A full size key leaves x mod about 2^85 and a kangaroo interval of about 2^40 for each
+// of the four sign combinations. Takes 5 to 7 minutes with --release on a single core
+#[test]
+#[ignore]
+fn challenge_60_test() {
+    use challenge_set_8_answers::challenge_60::{CURVE_ORDER, FACTOR_BOUND, PARAMETERS};
+
+    let oracle = set_8::create_ecdh_ladder_oracle(&PARAMETERS, None)
+        .expect("Challenge 60: could not create oracle");
+
+    let x =
+        set_8::recover_ecdh_private_key_from_twist(&oracle, CURVE_ORDER, FACTOR_BOUND, None, None)
+            .expect("Challenge 60: could not recover private key");
+
+    assert_eq!(oracle.ecdh.x.to_str_radix(10), x);
+}
+
+#[test]
+fn challenge_61_ecdsa_test() {
+    use challenge_set_8_answers::challenge_61::{MESSAGE, PARAMETERS};
+
+    let ecdsa = set_8::ecdsa_generate_key_pair(&PARAMETERS)
+        .expect("Challenge 61: could not generate key pair");
+    let public_key = ecdsa.public_key();
+    let signature = set_8::ecdsa_sign_message(&ecdsa, MESSAGE);
+
+    let forged_ecdsa = set_8::forge_ecdsa_key_for_signature(&public_key, MESSAGE, &signature)
+        .expect("Challenge 61: could not forge key pair");
+    let forged_public_key = forged_ecdsa.public_key();
+
+    assert_ne!(public_key, forged_public_key);
+    assert!(set_8::ecdsa_verify_signature(
+        &public_key,
+        MESSAGE,
+        &signature
+    ));
+    assert!(set_8::ecdsa_verify_signature(
+        &forged_public_key,
+        MESSAGE,
+        &signature
+    ));
+    assert!(set_8::ecdsa_verify_signature(
+        &forged_public_key,
+        MESSAGE,
+        &set_8::ecdsa_sign_message(&forged_ecdsa, MESSAGE)
+    ));
+}
+
+#[test]
+fn challenge_61_rsa_test() {
+    use challenge_set_8_answers::challenge_61::{MESSAGE, RSA_BITS};
+
+    let rsa =
+        set_6::generate_rsa_key_pair(RSA_BITS).expect("Challenge 61: could not generate key pair");
+    let public_key = rsa.public_key();
+    let signature = set_6::rsa_sign_message(&rsa, MESSAGE, HashAlgorithm::Sha256)
+        .expect("Challenge 61: could not sign message");
+
+    let forged_rsa =
+        set_8::forge_rsa_key_for_signature(&public_key, MESSAGE, &signature, HashAlgorithm::Sha256)
+            .expect("Challenge 61: could not forge key pair");
+    let forged_public_key = forged_rsa.public_key();
+
+    assert_ne!(public_key, forged_public_key);
+    assert!(set_6::rsa_verify_signature(
+        &forged_public_key,
+        MESSAGE,
+        &signature,
+        HashAlgorithm::Sha256
+    ));
+    assert_eq!(
+        signature,
+        set_6::rsa_sign_message(&forged_rsa, MESSAGE, HashAlgorithm::Sha256)
+            .expect("Challenge 61: could not sign message")
+    );
+}
+
+#[test]
+fn challenge_62_test() {
+    use challenge_set_8_answers::challenge_62::{PARAMETERS, SIGNATURE_COUNT, ZERO_BITS};
+
+    let oracle = set_8::create_biased_nonce_ecdsa_oracle(&PARAMETERS, ZERO_BITS)
+        .expect("Challenge 62: could not create oracle");
+
+    let d = set_8::recover_ecdsa_private_key_from_biased_nonces(&oracle, SIGNATURE_COUNT)
+        .expect("Challenge 62: could not recover private key");
+
+    assert_eq!(oracle.ecdsa.d.to_str_radix(10), d);
+}
+
+#[test]
+fn challenge_63_key_test() {
+    use challenge_set_8_answers::challenge_63::{AAD, PLAINTEXTS};
+
+    let oracle = set_8::create_gcm_oracle();
+
+    let h = set_8::recover_gcm_authentication_key(&oracle, AAD, &PLAINTEXTS)
+        .expect("Challenge 63: could not recover authentication key");
+
+    assert_eq!(
+        hex::encode(
+            aes::gcm_authentication_key(&oracle.key)
+                .expect("Challenge 63: could not compute authentication key")
+                .to_bytes()
+        ),
+        h
+    );
+}
+
+#[test]
+fn challenge_63_forgery_test() {
+    use challenge_set_8_answers::challenge_63::{AAD, FORGED_AAD, FORGED_PLAINTEXT, PLAINTEXTS};
+
+    let oracle = set_8::create_gcm_oracle();
+
+    let plaintext = set_8::forge_gcm_message_from_nonce_reuse(
+        &oracle,
+        AAD,
+        &PLAINTEXTS,
+        FORGED_AAD,
+        FORGED_PLAINTEXT,
+    )
+    .expect("Challenge 63: could not forge message");
+
+    assert_eq!(FORGED_PLAINTEXT, plaintext);
+}
+
+fn gcm_authentication_key(oracle: &GcmOracle) -> String {
+    hex::encode(
+        aes::gcm_authentication_key(&oracle.key)
+            .expect("could not compute authentication key")
+            .to_bytes(),
+    )
+}
+
+#[test]
+fn challenge_64_short_test() {
+    use challenge_set_8_answers::challenge_64::{SHORT_BLOCK_EXPONENT, SHORT_TAG_LEN};
+
+    let oracle = set_8::create_truncated_gcm_oracle(SHORT_TAG_LEN);
+
+    let h = set_8::recover_gcm_key_from_truncated_tags(&oracle, SHORT_BLOCK_EXPONENT)
+        .expect("Challenge 64: could not recover authentication key");
+
+    assert_eq!(gcm_authentication_key(&oracle), h);
+}
+
+// The 32-bit tags the challenge asks for. The first forgery alone takes about 2^16
+// attempts against 2^17 block messages, about 5 minutes with --release on a single core
+#[test]
+#[ignore]
+fn challenge_64_test() {
+    use challenge_set_8_answers::challenge_64::{BLOCK_EXPONENT, TAG_LEN};
+
+    let oracle = set_8::create_truncated_gcm_oracle(TAG_LEN);
+
+    let h = set_8::recover_gcm_key_from_truncated_tags(&oracle, BLOCK_EXPONENT)
+        .expect("Challenge 64: could not recover authentication key");
+
+    assert_eq!(gcm_authentication_key(&oracle), h);
+}
+
+#[test]
+fn challenge_65_short_test() {
+    use challenge_set_8_answers::challenge_65::{SHORT_BLOCK_EXPONENT, SHORT_TAG_LEN};
+
+    let oracle = set_8::create_truncated_gcm_oracle(SHORT_TAG_LEN);
+
+    let h = set_8::recover_gcm_key_from_length_extension(&oracle, SHORT_BLOCK_EXPONENT)
+        .expect("Challenge 65: could not recover authentication key");
+
+    assert_eq!(gcm_authentication_key(&oracle), h);
+}
+
+// 32-bit tags again, now over 2^17 block forgeries of the empty message. Takes about
+// 18 minutes with --release on a single core
+#[test]
+#[ignore]
+fn challenge_65_test() {
+    use challenge_set_8_answers::challenge_65::{BLOCK_EXPONENT, TAG_LEN};
+
+    let oracle = set_8::create_truncated_gcm_oracle(TAG_LEN);
+
+    let h = set_8::recover_gcm_key_from_length_extension(&oracle, BLOCK_EXPONENT)
+        .expect("Challenge 65: could not recover authentication key");
+
+    assert_eq!(gcm_authentication_key(&oracle), h);
+}
@@ -0,0 +1,110 @@
+extern crate matasano;
+extern crate num_bigint;
+
+use matasano::dlog::{self, KangarooParameters, MultiplicativeGroup};
+use matasano::ec::weierstrass::{Point, WeierstrassCurve};
+use num_bigint::BigUint;
+
+#[test]
+fn dlog_pohlig_hellman_prime_powers_test() {
+    // 43243201 - 1 = 2^6 * 3^3 * 5^2 * 7 * 11 * 13, and 34 is a primitive root
+    let p = BigUint::from(43_243_201u32);
+    let g = BigUint::from(34u32);
+    let order_factors: Vec<(BigUint, u32)> = [(2u32, 6), (3, 3), (5, 2), (7, 1), (11, 1), (13, 1)]
+        .iter()
+        .map(|&(prime, exponent)| (BigUint::from(prime), exponent))
+        .collect();
+    let group = MultiplicativeGroup::new(&p);
+
+    for x in &[0u32, 1, 2, 1_000, 43_243_199] {
+        let x = BigUint::from(*x);
+        let y = g.modpow(&x, &p);
+
+        assert_eq!(
+            Some(x),
+            dlog::pohlig_hellman(&group, &g, &y, &order_factors)
+                .expect("could not take discrete log")
+        );
+    }
+
+    // 34^2 generates the index 2 subgroup, which leaves out 34 itself
+    let square = (&g * &g) % &p;
+    let half_order_factors: Vec<(BigUint, u32)> = order_factors
+        .iter()
+        .map(|(prime, exponent)| match prime == &BigUint::from(2u32) {
+            true => (prime.clone(), exponent - 1),
+            false => (prime.clone(), *exponent),
+        })
+        .collect();
+
+    assert_eq!(
+        None,
+        dlog::pohlig_hellman(&group, &square, &g, &half_order_factors)
+            .expect("could not take discrete log")
+    );
+}
+
+#[test]
+fn dlog_pohlig_hellman_curve_test() {
+    // y^2 = x^3 - 95051x + 210 has order 2^2 * 3 * 11 * 23 * 31 * 89 * 4999 * 28411 * 45361 *
+    // 109138087 * 39726369581
+    let p = BigUint::parse_bytes(b"233970423115425145524320034830162017933", 10)
+        .expect("could not parse p");
+    let curve = WeierstrassCurve::new(&p - 95051u32, BigUint::from(210u32), p)
+        .expect("could not create curve");
+    let curve_order = BigUint::parse_bytes(b"233970423115425145550826547352470124412", 10)
+        .expect("could not parse curve order");
+    let primes = [3u32, 11, 23, 31, 89, 4999];
+    let order: BigUint = primes.iter().map(|&prime| BigUint::from(prime)).product();
+    let order_factors: Vec<(BigUint, u32)> = primes
+        .iter()
+        .map(|&prime| (BigUint::from(prime), 1))
+        .collect();
+
+    let g = primes.iter().fold(Point::Infinity, |sum, &prime| {
+        let point = curve
+            .point_of_order(&curve_order, &BigUint::from(prime))
+            .expect("could not find point");
+        curve.add(&sum, &point)
+    });
+    let x = BigUint::from(123_456_789u32) % &order;
+    let y = curve.scale(&g, &x);
+
+    assert_eq!(
+        Some(x),
+        dlog::pohlig_hellman(&curve, &g, &y, &order_factors).expect("could not take discrete log")
+    );
+}
+
+#[test]
+fn dlog_kangaroo_test() {
+    // 34 generates the whole group mod 43243201, which is far larger than the interval
+    let p = BigUint::from(43_243_201u32);
+    let g = BigUint::from(34u32);
+    let group = MultiplicativeGroup::new(&p);
+    let lower = BigUint::from(100_000u32);
+    let upper = BigUint::from(1_148_576u32);
+    let x = BigUint::from(777_777u32);
+    let y = g.modpow(&x, &p);
+    let parameters = KangarooParameters::for_interval(&(&upper - &lower));
+
+    // Any single walk may miss, but a walk that lands in the trap has the right answer
+    let mut attempt = parameters;
+
+    for _ in 0..8 {
+        if let Some(found) = dlog::kangaroo(&group, &g, &y, &lower, &upper, &attempt)
+            .expect("could not take discrete log")
+        {
+            assert_eq!(x, found);
+        }
+
+        attempt = attempt.perturbed();
+    }
+
+    assert_eq!(
+        Some(x),
+        dlog::kangaroo_with_retries(&group, &g, &y, &lower, &upper, &parameters)
+            .expect("could not take discrete log")
+    );
+    assert!(dlog::kangaroo(&group, &g, &y, &upper, &lower, &parameters).is_err());
+}
@@ -0,0 +1,126 @@
+extern crate matasano;
+extern crate num_bigint;
+extern crate num_traits;
+
+use matasano::ec::montgomery::MontgomeryCurve;
+use matasano::ec::weierstrass::{Point, WeierstrassCurve};
+use matasano::utility::number;
+use num_bigint::BigUint;
+use num_traits::Num;
+
+static P: &'static str = "233970423115425145524320034830162017933";
+static GX: &'static str = "182";
+static GY: &'static str = "85518893674295321206118380980485522083";
+static ORDER: &'static str = "29246302889428143187362802287225875743";
+
+fn decimal(value: &str) -> BigUint {
+    BigUint::from_str_radix(value, 10).expect("invalid decimal")
+}
+
+fn curve() -> WeierstrassCurve {
+    let p = decimal(P);
+    WeierstrassCurve::new(&p - 95051u32, BigUint::from(11279326u32), p).expect("invalid curve")
+}
+
+fn generator() -> Point {
+    Point::Affine(decimal(GX), decimal(GY))
+}
+
+fn montgomery_curve() -> MontgomeryCurve {
+    MontgomeryCurve::new(BigUint::from(534u32), BigUint::from(1u32), decimal(P))
+        .expect("invalid curve")
+}
+
+#[test]
+fn ec_weierstrass_group_law_test() {
+    let curve = curve();
+    let g = generator();
+
+    assert!(curve.is_on_curve(&g));
+    assert_eq!(Point::Infinity, curve.scale(&g, &decimal(ORDER)));
+    assert_eq!(Point::Infinity, curve.add(&g, &curve.negate(&g)));
+    assert_eq!(g, curve.add(&g, &Point::Infinity));
+
+    let mut sum = Point::Infinity;
+
+    for k in 1u32..20 {
+        sum = curve.add(&sum, &g);
+
+        assert!(curve.is_on_curve(&sum));
+        assert_eq!(sum, curve.scale(&g, &BigUint::from(k)));
+    }
+
+    assert_eq!(curve.double(&g), curve.add(&g, &g));
+}
+
+#[test]
+fn ec_weierstrass_validation_test() {
+    let curve = curve();
+    let order = decimal(ORDER);
+
+    assert!(curve.validate(&generator(), &order).is_ok());
+    assert!(curve.validate(&Point::Infinity, &order).is_err());
+    assert!(curve
+        .validate(&Point::Affine(decimal(GX), BigUint::from(1u32)), &order)
+        .is_err());
+
+    let point = curve.random_point();
+
+    assert!(curve.is_on_curve(&point));
+    assert!(WeierstrassCurve::new(BigUint::from(0u32), BigUint::from(0u32), decimal(P)).is_err());
+}
+
+#[test]
+fn ec_sqrt_mod_test() {
+    let p = decimal(P);
+
+    for value in 1u32..50 {
+        let square = BigUint::from(value) * BigUint::from(value) % &p;
+        let root = number::sqrt_mod(&square, &p).expect("no square root");
+
+        assert_eq!(square, &root * &root % &p);
+    }
+
+    assert_eq!(
+        None,
+        number::sqrt_mod(&BigUint::from(3u32), &BigUint::from(7u32))
+    );
+    let root = number::sqrt_mod(&BigUint::from(2u32), &BigUint::from(17u32));
+
+    assert!(root == Some(BigUint::from(6u32)) || root == Some(BigUint::from(11u32)));
+}
+
+#[test]
+fn ec_montgomery_ladder_test() {
+    let curve = curve();
+    let montgomery = montgomery_curve();
+    let u = BigUint::from(4u32);
+
+    assert_eq!(
+        curve,
+        montgomery.to_weierstrass().expect("invalid conversion")
+    );
+    assert_eq!(decimal(GX), montgomery.u_to_weierstrass_x(&u));
+    assert!(montgomery.is_valid_u(&u));
+    assert_eq!(BigUint::from(0u32), montgomery.ladder(&u, &decimal(ORDER)));
+
+    for k in &[1u32, 2, 3, 1000, 123_456_789] {
+        let k = BigUint::from(*k);
+
+        match curve.scale(&generator(), &k) {
+            Point::Affine(x, _) => {
+                assert_eq!(montgomery.weierstrass_x_to_u(&x), montgomery.ladder(&u, &k))
+            }
+            Point::Infinity => panic!("unexpected point at infinity"),
+        }
+    }
+
+    let v = montgomery.v_from_u(&u).expect("no v coordinate");
+
+    assert!(montgomery.is_on_curve(&u, &v));
+
+    let point = montgomery.point_to_weierstrass(&u, &v);
+
+    assert!(curve.is_on_curve(&point));
+    assert_eq!(Some((u, v)), montgomery.point_from_weierstrass(&point));
+}
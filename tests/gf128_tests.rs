@@ -0,0 +1,267 @@
+extern crate hex;
+extern crate matasano;
+extern crate rand;
+
+use matasano::aes::{self, GcmMessage};
+use matasano::gf128::polynomial::{self, Polynomial};
+use matasano::gf128::{self, Element, Multiplier};
+
+// NIST GCM test case 4
+static KEY: &'static str = "feffe9928665731c6d6a8f9467308308";
+static NONCE: &'static str = "cafebabefacedbaddecaf888";
+static PLAINTEXT: &'static str = "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+                                  1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39";
+static AAD: &'static str = "feedfacedeadbeeffeedfacedeadbeefabaddad2";
+static CIPHERTEXT: &'static str =
+    "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e\
+     21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091";
+static TAG: &'static str = "5bc94fbc3221a5db94fae95ae7121a47";
+
+fn element(value: &str) -> Element {
+    Element::from_bytes(&hex::decode(value).expect("invalid hex")).expect("invalid element")
+}
+
+fn linear(root: Element) -> Polynomial {
+    Polynomial::new(vec![root, Element::one()])
+}
+
+fn product(factors: &[(Polynomial, usize)]) -> Polynomial {
+    factors
+        .iter()
+        .fold(Polynomial::one(), |result, &(ref factor, multiplicity)| {
+            (0..multiplicity).fold(result, |result, _| &result * factor)
+        })
+}
+
+fn random_elements(count: usize) -> Vec<Element> {
+    let mut rng = rand::thread_rng();
+
+    (0..count).map(|_| Element::random(&mut rng)).collect()
+}
+
+// x^2 + x + c is irreducible exactly when c has absolute trace 1
+fn random_irreducible_quadratic() -> Polynomial {
+    let mut rng = rand::thread_rng();
+
+    loop {
+        let c = Element::random(&mut rng);
+        let trace = (0..128)
+            .fold((Element::zero(), c), |(sum, power), _| {
+                (sum + power, power.square())
+            })
+            .0;
+
+        if trace == Element::one() {
+            return Polynomial::new(vec![c, Element::one(), Element::one()]);
+        }
+    }
+}
+
+#[test]
+fn gf128_element_test() {
+    let elements = random_elements(3);
+    let (a, b, c) = (elements[0], elements[1], elements[2]);
+
+    assert_eq!(a * (b + c), a * b + a * c);
+    assert_eq!(a * b, Multiplier::new(b).multiply(a));
+    assert_eq!(Element::one(), a * a.inverse().expect("could not invert"));
+    assert_eq!(a, a.square().sqrt());
+    assert_eq!(
+        a,
+        Element::from_bytes(&a.to_bytes()).expect("invalid element")
+    );
+    assert!(Element::zero().inverse().is_err());
+
+    // The reflected bit order puts x^0 in the high bit of the first byte
+    assert_eq!(Element::one(), element("80000000000000000000000000000000"));
+    assert_eq!(
+        element("e1000000000000000000000000000000"),
+        element("00000000000000000000000000000001") * element("40000000000000000000000000000000")
+    );
+}
+
+#[test]
+fn gf128_linear_map_test() {
+    let elements = random_elements(2);
+    let (c, h) = (elements[0], elements[1]);
+
+    let product = gf128::multiplication_matrix(c)
+        .expect("could not build multiplication matrix")
+        .multiply_vector(&h.to_bits())
+        .expect("could not multiply");
+    let square = gf128::squaring_matrix()
+        .expect("could not build squaring matrix")
+        .multiply_vector(&h.to_bits())
+        .expect("could not multiply");
+
+    assert_eq!(c * h, Element::from_bits(&product).expect("invalid bits"));
+    assert_eq!(
+        h.square(),
+        Element::from_bits(&square).expect("invalid bits")
+    );
+}
+
+#[test]
+fn gf128_ghash_test() {
+    // NIST GCM test case 2
+    let h = element("66e94bd4ef8a2c3b884cfa59ca342b2e");
+    let ciphertext = hex::decode("0388dace60b6a392f328c2b971b2fe78").expect("invalid hex");
+
+    assert_eq!(
+        element("f38cbb1ad69223dcc3457ae5b6b0f885"),
+        gf128::ghash(h, &[], &ciphertext).expect("could not compute GHASH")
+    );
+}
+
+#[test]
+fn gf128_aes_gcm_test() {
+    let key = hex::decode(KEY).expect("invalid hex");
+    let nonce = hex::decode(NONCE).expect("invalid hex");
+    let plaintext = hex::decode(PLAINTEXT).expect("invalid hex");
+    let aad = hex::decode(AAD).expect("invalid hex");
+
+    let message = aes::encrypt_gcm_text(&plaintext, &aad, &key, &nonce).expect("could not encrypt");
+
+    assert_eq!(CIPHERTEXT, hex::encode(&message.ciphertext));
+    assert_eq!(TAG, hex::encode(&message.tag));
+    assert_eq!(
+        plaintext,
+        aes::decrypt_gcm_text(&message, &key, &nonce).expect("could not decrypt")
+    );
+
+    let truncated = GcmMessage {
+        aad: message.aad.clone(),
+        ciphertext: message.ciphertext.clone(),
+        tag: message.tag[..4].to_vec(),
+    };
+    assert!(aes::decrypt_gcm_text(&truncated, &key, &nonce).is_ok());
+
+    let mut forged = truncated;
+    forged.ciphertext[0] ^= 1;
+    assert!(aes::decrypt_gcm_text(&forged, &key, &nonce).is_err());
+}
+
+#[test]
+fn gf128_polynomial_div_rem_test() {
+    let coefficients = random_elements(9);
+    let dividend = Polynomial::new(coefficients[..6].to_vec());
+    let divisor = Polynomial::new(coefficients[6..].to_vec());
+
+    let (quotient, remainder) = dividend.div_rem(&divisor).expect("could not divide");
+
+    assert_eq!(Some(3), quotient.degree());
+    assert!(remainder.degree().unwrap_or(0) < 2);
+    assert_eq!(dividend, &(&quotient * &divisor) + &remainder);
+    assert!(dividend.div_rem(&Polynomial::zero()).is_err());
+}
+
+#[test]
+fn gf128_polynomial_gcd_test() {
+    let roots = random_elements(4);
+    let common = &linear(roots[0]) * &linear(roots[1]);
+    let first = &common * &linear(roots[2]);
+    let second = &common * &linear(roots[3]);
+
+    assert_eq!(common, first.gcd(&second).expect("could not compute gcd"));
+    assert_eq!(
+        Polynomial::one(),
+        linear(roots[2])
+            .gcd(&linear(roots[3]))
+            .expect("could not compute gcd")
+    );
+}
+
+#[test]
+fn gf128_polynomial_square_free_factorization_test() {
+    let roots = random_elements(3);
+    let quadratic = random_irreducible_quadratic();
+    let expected = vec![
+        (linear(roots[0]), 1),
+        (linear(roots[1]), 2),
+        (quadratic, 3),
+        (linear(roots[2]), 4),
+    ];
+    let f = product(&expected).scale(roots[0]);
+
+    let factors = polynomial::square_free_factorization(&f).expect("could not factor square free");
+
+    assert_eq!(f.monic().expect("could not make monic"), product(&factors));
+
+    for &(ref factor, multiplicity) in &expected {
+        assert!(factors.iter().any(|&(ref square_free, m)| {
+            m == multiplicity && square_free.rem(factor).expect("could not reduce").is_zero()
+        }));
+    }
+}
+
+#[test]
+fn gf128_polynomial_distinct_degree_factorization_test() {
+    let roots = random_elements(2);
+    let linear_part = &linear(roots[0]) * &linear(roots[1]);
+    let quadratic_part = &random_irreducible_quadratic() * &random_irreducible_quadratic();
+    let f = &linear_part * &quadratic_part;
+
+    let factors =
+        polynomial::distinct_degree_factorization(&f).expect("could not factor distinct degree");
+
+    assert_eq!(vec![(linear_part, 1), (quadratic_part, 2)], factors);
+}
+
+#[test]
+fn gf128_polynomial_equal_degree_factorization_test() {
+    let quadratics: Vec<Polynomial> = (0..3).map(|_| random_irreducible_quadratic()).collect();
+    let f = product(
+        &quadratics
+            .iter()
+            .map(|quadratic| (quadratic.clone(), 1))
+            .collect::<Vec<_>>(),
+    );
+
+    let factors =
+        polynomial::equal_degree_factorization(&f, 2).expect("could not factor equal degree");
+
+    assert_eq!(3, factors.len());
+
+    for quadratic in &quadratics {
+        assert!(factors.contains(quadratic));
+    }
+}
+
+#[test]
+fn gf128_polynomial_factor_test() {
+    let roots = random_elements(2);
+    let quadratic = random_irreducible_quadratic();
+    let f = product(&[
+        (linear(roots[0]), 2),
+        (linear(roots[1]), 1),
+        (quadratic.clone(), 1),
+    ]);
+
+    let factors = polynomial::factor(&f).expect("could not factor");
+
+    assert_eq!(f, product(&factors));
+    assert_eq!(3, factors.len());
+    assert!(factors.contains(&(linear(roots[0]), 2)));
+    assert!(factors.contains(&(linear(roots[1]), 1)));
+    assert!(factors.contains(&(quadratic, 1)));
+}
+
+#[test]
+fn gf128_polynomial_roots_test() {
+    let mut roots = random_elements(3);
+    let f = product(&[
+        (linear(roots[0]), 1),
+        (linear(roots[1]), 2),
+        (linear(roots[2]), 1),
+        (random_irreducible_quadratic(), 1),
+    ]);
+
+    let found = polynomial::roots(&f).expect("could not find roots");
+    roots.sort();
+
+    assert_eq!(roots, found);
+
+    for &root in &found {
+        assert!(f.evaluate(root).is_zero());
+    }
+}
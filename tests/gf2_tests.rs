@@ -0,0 +1,98 @@
+extern crate matasano;
+extern crate rand;
+
+use matasano::gf2::Matrix;
+use rand::Rng;
+
+fn random_matrix(row_count: usize, column_count: usize) -> Matrix {
+    let mut rng = rand::thread_rng();
+    let rows: Vec<Vec<bool>> = (0..row_count)
+        .map(|_| (0..column_count).map(|_| rng.gen()).collect())
+        .collect();
+
+    Matrix::from_rows(&rows).expect("could not build matrix")
+}
+
+fn matrix(rows: &[&str]) -> Matrix {
+    let rows: Vec<Vec<bool>> = rows
+        .iter()
+        .map(|row| row.chars().map(|c| c == '1').collect())
+        .collect();
+
+    Matrix::from_rows(&rows).expect("could not build matrix")
+}
+
+#[test]
+fn gf2_multiply_test() {
+    let a = random_matrix(70, 130);
+    let b = random_matrix(130, 90);
+    let product = a.multiply(&b).expect("could not multiply");
+
+    assert_eq!(
+        a,
+        a.multiply(&Matrix::identity(130))
+            .expect("could not multiply")
+    );
+    assert_eq!(
+        product.transpose(),
+        b.transpose()
+            .multiply(&a.transpose())
+            .expect("could not multiply")
+    );
+    assert_eq!(
+        product.column(5),
+        a.multiply_vector(&b.column(5))
+            .expect("could not multiply vector")
+    );
+    assert!(b.multiply(&b).is_err());
+}
+
+#[test]
+fn gf2_reduced_row_echelon_test() {
+    let (reduced, pivots) = matrix(&["0110", "1011", "1101"]).reduced_row_echelon();
+
+    assert_eq!(vec![0, 1], pivots);
+    assert_eq!(matrix(&["1011", "0110", "0000"]), reduced);
+}
+
+#[test]
+fn gf2_kernel_test() {
+    let a = random_matrix(100, 150);
+    let kernel = a.kernel();
+
+    assert_eq!(150 - a.rank(), kernel.row_count);
+    assert_eq!(kernel.row_count, kernel.rank());
+    assert!(a
+        .multiply(&kernel.transpose())
+        .expect("could not multiply")
+        .rows
+        .iter()
+        .all(|row| row.iter().all(|&word| word == 0)));
+}
+
+#[test]
+fn gf2_solve_test() {
+    let mut rng = rand::thread_rng();
+    let a = random_matrix(120, 100);
+    let x: Vec<bool> = (0..100).map(|_| rng.gen()).collect();
+    let b = a.multiply_vector(&x).expect("could not multiply vector");
+
+    let solution = a
+        .solve(&b)
+        .expect("could not solve")
+        .expect("no solution found");
+
+    assert_eq!(
+        b,
+        a.multiply_vector(&solution)
+            .expect("could not multiply vector")
+    );
+
+    // x + y = 1 and x + y = 0 have no common solution
+    assert_eq!(
+        None,
+        matrix(&["11", "11"])
+            .solve(&[true, false])
+            .expect("could not solve")
+    );
+}
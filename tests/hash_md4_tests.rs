@@ -0,0 +1,22 @@
+extern crate hex;
+extern crate matasano;
+
+use matasano::hash::md4;
+
+#[test]
+fn md4_test_vectors_test() {
+    let vectors = [
+        ("", "31d6cfe0d16ae931b73c59d7e0c089c0"),
+        ("a", "bde52cb31de33e46245e05fbdbd6fb24"),
+        ("abc", "a448017aaf21d8525fc10ae87aa6729d"),
+        ("message digest", "d9130a8164549fe818874806e1c7014b"),
+        (
+            "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+            "e33b4ddc9c38f2199c3e7b164fcc0536",
+        ),
+    ];
+
+    for &(message, digest) in &vectors {
+        assert_eq!(digest, hex::encode(md4::md4(message.as_bytes())));
+    }
+}
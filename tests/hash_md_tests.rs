@@ -0,0 +1,63 @@
+extern crate matasano;
+
+use matasano::hash::md::{AesCompression, MdBuilder};
+
+#[test]
+fn md_hash_output_size_test() {
+    for &bits in &[16, 24, 32] {
+        let hash = MdBuilder::new(AesCompression::new(bits).expect("could not create compression"))
+            .build()
+            .expect("could not build hash");
+
+        let digest = hash
+            .hash(b"YELLOW SUBMARINE")
+            .expect("could not hash message");
+
+        assert_eq!(bits / 8, digest.len());
+        assert_eq!(
+            digest,
+            hash.hash(b"YELLOW SUBMARINE")
+                .expect("could not hash message")
+        );
+    }
+}
+
+#[test]
+fn md_hash_length_padding_test() {
+    let compression = AesCompression::new(16).expect("could not create compression");
+    let hash = MdBuilder::new(compression.clone())
+        .build()
+        .expect("could not build hash");
+    let unpadded_hash = MdBuilder::new(compression)
+        .length_padding(false)
+        .build()
+        .expect("could not build hash");
+
+    assert_eq!(32, hash.pad(b"YELLOW SUBMARINE").len());
+    assert_eq!(16, unpadded_hash.pad(b"YELLOW SUBMARINE").len());
+    assert_eq!(
+        unpadded_hash
+            .hash(b"YELLOW")
+            .expect("could not hash message"),
+        unpadded_hash
+            .hash(b"YELLOW\x00")
+            .expect("could not hash message")
+    );
+    assert!(
+        hash.hash(b"YELLOW").expect("could not hash message")
+            != hash.hash(b"YELLOW\x00").expect("could not hash message")
+    );
+}
+
+#[test]
+fn md_hash_invalid_parameters_test() {
+    assert!(AesCompression::new(12).is_err());
+    assert!(AesCompression::new(136).is_err());
+
+    let compression = AesCompression::new(16).expect("could not create compression");
+
+    assert!(MdBuilder::new(compression)
+        .initial_state(&[0; 3])
+        .build()
+        .is_err());
+}
@@ -0,0 +1,84 @@
+extern crate matasano;
+extern crate num_bigint;
+extern crate num_rational;
+
+use matasano::lattice;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+
+fn rational(numerator: i64, denominator: i64) -> BigRational {
+    BigRational::new(BigInt::from(numerator), BigInt::from(denominator))
+}
+
+fn basis(rows: &[[(i64, i64); 4]]) -> Vec<Vec<BigRational>> {
+    rows.iter()
+        .map(|row| row.iter().map(|&(n, d)| rational(n, d)).collect())
+        .collect()
+}
+
+#[test]
+fn lattice_lll_test() {
+    let delta = rational(99, 100);
+    let original = basis(&[
+        [(-2, 1), (0, 1), (2, 1), (0, 1)],
+        [(1, 2), (-1, 1), (0, 1), (0, 1)],
+        [(-1, 1), (0, 1), (-2, 1), (1, 2)],
+        [(-1, 1), (1, 1), (1, 1), (2, 1)],
+    ]);
+    let expected = basis(&[
+        [(1, 2), (-1, 1), (0, 1), (0, 1)],
+        [(-1, 1), (0, 1), (-2, 1), (1, 2)],
+        [(-1, 2), (0, 1), (1, 1), (2, 1)],
+        [(-3, 2), (-1, 1), (2, 1), (0, 1)],
+    ]);
+
+    assert!(!lattice::is_lll_reduced(&original, &delta));
+
+    let reduced = lattice::lll(&original, &delta).expect("could not reduce basis");
+
+    assert_eq!(expected, reduced);
+    assert!(lattice::is_lll_reduced(&reduced, &delta));
+}
+
+#[test]
+fn lattice_gram_schmidt_test() {
+    let orthogonal = lattice::gram_schmidt(&basis(&[
+        [(3, 1), (1, 1), (0, 1), (0, 1)],
+        [(2, 1), (2, 1), (0, 1), (0, 1)],
+        [(1, 1), (1, 1), (1, 1), (0, 1)],
+        [(0, 1), (1, 1), (1, 1), (1, 1)],
+    ]));
+
+    for i in 0..orthogonal.len() {
+        for j in 0..i {
+            assert_eq!(
+                rational(0, 1),
+                lattice::inner_product(&orthogonal[i], &orthogonal[j])
+            );
+        }
+    }
+
+    assert_eq!(orthogonal[0], basis(&[[(3, 1), (1, 1), (0, 1), (0, 1)]])[0]);
+}
+
+#[test]
+fn lattice_lll_rejects_dependent_basis_test() {
+    let dependent = basis(&[
+        [(1, 1), (2, 1), (3, 1), (4, 1)],
+        [(2, 1), (4, 1), (6, 1), (8, 1)],
+        [(0, 1), (1, 1), (0, 1), (0, 1)],
+        [(0, 1), (0, 1), (0, 1), (1, 1)],
+    ]);
+
+    // No two of these are parallel, but the third is the sum of the other two
+    let sum = basis(&[
+        [(0, 1), (1, 1), (0, 1), (0, 1)],
+        [(0, 1), (0, 1), (0, 1), (1, 1)],
+        [(0, 1), (1, 1), (0, 1), (1, 1)],
+    ]);
+
+    assert!(lattice::lll(&dependent, &rational(3, 4)).is_err());
+    assert!(lattice::lll(&sum, &rational(3, 4)).is_err());
+    assert!(lattice::lll(&sum[..2], &rational(3, 4)).is_ok());
+    assert!(lattice::lll(&sum[..2], &rational(1, 4)).is_err());
+}
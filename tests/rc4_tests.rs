@@ -0,0 +1,20 @@
+extern crate hex;
+extern crate matasano;
+
+use matasano::rc4;
+
+#[test]
+fn rc4_test_vectors_test() {
+    let vectors = [
+        ("Key", "Plaintext", "bbf316e8d940af0ad3"),
+        ("Wiki", "pedia", "1021bf0420"),
+        ("Secret", "Attack at dawn", "45a01f645fc35b383552544b9bf5"),
+    ];
+
+    for &(key, plaintext, ciphertext) in &vectors {
+        let encrypted = rc4::rc4_text(plaintext.as_bytes(), key.as_bytes())
+            .expect("could not encrypt plaintext");
+
+        assert_eq!(ciphertext, hex::encode(encrypted));
+    }
+}